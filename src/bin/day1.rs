@@ -1,98 +1,88 @@
 use std::env;
+use std::error::Error;
 use std::fs;
 use std::process;
-use std::error::Error;
+
+use advent_of_code::day1;
+use advent_of_code::runner::{self, Format};
+use advent_of_code::solution;
+use advent_of_code::util::{parse_args, print_extra};
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    let file_path = parse_args(&args).unwrap_or_else(|err| {
+    let mut args: Vec<String> = env::args().collect();
+    let (file_path, format) = parse_args(&mut args).unwrap_or_else(|err| {
         println!("Problem parsing arguments: {err}");
         process::exit(1);
     });
-    
-    if let Err(e) = run(file_path) {
-        println!("Application error: {e}");
+    let options = parse_options(&args).unwrap_or_else(|err| {
+        println!("Problem parsing arguments: {err}");
         process::exit(1);
-    }
-    let d1 = 299;
-    let d2 = 100;
-    let d = d1/d2;
-    println!("modtest: {d}")
-    // println!("Instructions:\n{raw_contents}");
-    // dbg!(args);
-}
+    });
 
-fn parse_args(args: &[String]) ->  Result<&str, &'static str> {
-    if args.len() < 2 {
-        return Err("Not enough arguments.");
+    let day = solution::find(1).expect("day 1 is registered");
+    let skip_blank = options.skip_blank;
+    let solved = runner::print_day(day, &file_path, format, |raw| {
+        solution::run_with::<day1::Solver>(raw, |raw| Ok(day1::parse_with(raw, skip_blank)?))
+    });
+    if !solved {
+        process::exit(1);
     }
-    let file_path = &args[1];
-    println!("Loading: {file_path}");
-    Ok(file_path)
-}
-
-fn run(file_path: &str) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(file_path)?;
-    let mut pos = 50;
-    let mut count = 0;
-    for instruction in parse_input(&contents){
-        let (new_pos, passes) = rotate_lock(pos, instruction);
-        count += passes;
-        pos = new_pos;
+    if let Some(export) = options.export
+        && let Err(e) = export_trace(&file_path, export, skip_blank, format)
+    {
+        println!("Application error: {e}");
+        process::exit(1);
     }
-    println!("Count: {count}");
+}
 
+fn export_trace(
+    file_path: &str,
+    export: Export,
+    skip_blank: bool,
+    format: Format,
+) -> Result<(), Box<dyn Error>> {
+    let steps = day1::trace(&fs::read_to_string(file_path)?, skip_blank)?;
+    let (path, trace) = match export {
+        Export::Csv(path) => (path, day1::trace_csv(&steps)),
+        Export::JsonLines(path) => (path, day1::trace_json_lines(&steps)),
+    };
+    fs::write(&path, trace)?;
+    print_extra(format, format!("Wrote {path}"));
     Ok(())
 }
 
-fn rotate_lock(pos: i32, rotate: i32) -> (i32, i32) {
-    let dial_size = 100;
-    let mut count = (rotate / dial_size).abs();
-    let mut new_pos = pos + (rotate % 100);
-    if new_pos < 0{
-        if pos != 0{
-            count += 1;
-        }
-        new_pos+=dial_size;
-    }
-    else if new_pos > 99 {
-        new_pos %= dial_size;
-        if new_pos != 0{
-            count += 1;
-        }
-    }
-    if new_pos == 0 && pos!=0{
-        count+=1;
-    }
-    println!("Rotate {pos} by {rotate} to {new_pos}: passes {count} times.");
-    (new_pos, count)
+#[derive(Debug, PartialEq, Clone)]
+enum Export {
+    Csv(String),
+    JsonLines(String),
 }
 
-fn get_direction_and_val(s: &str) -> (Option<char>, i32) {
-    let mut chars = s.chars();
-    let dir = chars.next();
-    let val = chars.as_str().parse::<i32>().unwrap();
-    (dir, val)
+#[derive(Debug, PartialEq, Clone, Default)]
+struct Options {
+    export: Option<Export>,
+    skip_blank: bool,
 }
-fn parse_input(contents: &str) -> Vec<i32> {
-    let mut results = Vec::new();
-    for line in contents.lines() {
-        let (dir, mut v) = get_direction_and_val(line);
-        match dir {
-            Some(direction) => {
-                if direction == 'L'{
-                    v *= -1;
-                }
-                else if direction != 'R' {
-                    println!("Invalid direction found {direction}");
-                }
-                }
-            None => {
-                println!("Encountered empty line")
+
+fn parse_options(args: &[String]) -> Result<Options, &'static str> {
+    let mut options = Options::default();
+    let mut rest = args.iter().skip(2);
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--skip-blank" => options.skip_blank = true,
+            "--csv" | "--jsonl" => {
+                let path = rest.next().ok_or("Trace export expects an output file.")?;
+                options.export = Some(if arg == "--csv" {
+                    Export::Csv(path.clone())
+                } else {
+                    Export::JsonLines(path.clone())
+                });
+            }
+            _ => {
+                return Err(
+                    "Unknown option, expected --skip-blank, --csv <file> or --jsonl <file>.",
+                );
             }
         }
-        results.push(v);
-        // println!("Instruction: ({line}, {v})");
     }
-    results
-}
\ No newline at end of file
+    Ok(options)
+}
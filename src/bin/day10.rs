@@ -0,0 +1,70 @@
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::process;
+
+use advent_of_code::day10;
+use advent_of_code::runner::{self, Format};
+use advent_of_code::solution;
+use advent_of_code::util::{parse_args, print_extra};
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let (file_path, format) = parse_args(&mut args).unwrap_or_else(|err| {
+        println!("Problem parsing arguments: {err}");
+        process::exit(1);
+    });
+    let cross_check = parse_cross_check(&args).unwrap_or_else(|err| {
+        println!("Problem parsing arguments: {err}");
+        process::exit(1);
+    });
+
+    let day = solution::find(10).expect("day 10 is registered");
+    if !runner::print_day(day, &file_path, format, day.run) {
+        process::exit(1);
+    }
+    if cross_check && let Err(e) = run(&file_path, format) {
+        println!("Application error: {e}");
+        process::exit(1);
+    }
+}
+
+fn run(file_path: &str, format: Format) -> Result<(), Box<dyn Error>> {
+    let machines = day10::parse(&fs::read_to_string(file_path)?)?;
+    let disagreements = day10::cross_check(&machines);
+    for d in &disagreements {
+        print_extra(
+            format,
+            format!(
+                "Machine {}: elimination gave {}, search gave {}",
+                d.machine + 1,
+                describe(d.algebraic),
+                describe(d.search)
+            ),
+        );
+    }
+    print_extra(
+        format,
+        format!(
+            "Cross-checked {} machines, {} disagreements",
+            machines.len(),
+            disagreements.len()
+        ),
+    );
+    Ok(())
+}
+
+fn describe(result: Result<u32, &'static str>) -> String {
+    match result {
+        Ok(presses) => presses.to_string(),
+        Err(e) => format!("error ({e})"),
+    }
+}
+
+fn parse_cross_check(args: &[String]) -> Result<bool, &'static str> {
+    match args.get(2).map(|s| s.as_str()) {
+        None => Ok(false),
+        Some("--cross-check") => Ok(true),
+        Some(_) => Err("Unknown option, expected --cross-check."),
+    }
+}
@@ -2,143 +2,55 @@ use std::env;
 use std::error::Error;
 use std::fs;
 use std::process;
-use std::str::Chars;
 
-use advent_of_code::util::parse_args;
+use advent_of_code::day6::{self, ReadingOrder};
+use advent_of_code::runner::{self, Format};
+use advent_of_code::solution;
+use advent_of_code::util::{parse_args, print_extra};
 
-// -1 for every empty space add 1 to every square around a roll then count the squares with less than 4
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    let file_path = parse_args(&args).unwrap_or_else(|err| {
+    let mut args: Vec<String> = env::args().collect();
+    let (file_path, format) = parse_args(&mut args).unwrap_or_else(|err| {
+        println!("Problem parsing arguments: {err}");
+        process::exit(1);
+    });
+    let big = parse_big(&args).unwrap_or_else(|err| {
         println!("Problem parsing arguments: {err}");
         process::exit(1);
     });
-    let _ = run(file_path);
-}
-
-fn run(file_path: &str) -> Result<(), Box<dyn Error>> {
-    let equations = process_input_part2(file_path);
-    let mut total = 0;
-    for e in equations {
-        total += e.result();
-    }
-    println!("Total value: {total}");
-    Ok(())
-}
-
-fn process_input_part2(file_path: &str) -> Vec<Equation> {
-    let mut result: Vec<Equation> = vec![];
-    let contents = fs::read_to_string(file_path).unwrap();
-    let lines: Vec<&str> = contents.split_inclusive('\n').collect();
-    let mut as_chars: Vec<_> = lines.iter().map(|v| v.chars()).collect();
 
-    // dbg!(&as_chars);
-    let mut raw_columns: Vec<Vec<String>> = vec![];
-    let mut problem: Vec<String> = vec![];
-    for i in 0..lines[0].len() {
-        let mut placeholder: Vec<char> = vec![];
-        let mut preserve = false;
-        for v in &mut as_chars {
-            let char: char = v.next().unwrap();
-            if char.is_alphanumeric() {
-                preserve = true;
-            }
-            placeholder.push(char);
-        }
-        if preserve {
-            problem.push(placeholder.into_iter().collect());
-        } else {
-            if !problem.is_empty() {
-                raw_columns.push(problem.clone());
-                problem = vec![];
-            }
-        }
+    let day = solution::find(6).expect("day 6 is registered");
+    if !runner::print_day(day, &file_path, format, day.run) {
+        process::exit(1);
     }
-    for v in &raw_columns {
-        result.push(Equation::build(v));
+    if let Err(e) = big_totals(&file_path, big, format) {
+        println!("Application error: {e}");
+        process::exit(1);
     }
-    // dbg!(&result);
-    result
 }
 
-fn process_input_part1(file_path: &str) -> Vec<Equation> {
-    let contents = fs::read_to_string(file_path).unwrap();
-    let lines: Vec<&str> = contents.lines().collect();
-    let first_term_raw: Vec<u64> = process_line_to_u64(&lines[0]);
-    let sec_term_raw = process_line_to_u64(&lines[1]);
-    let third_term_raw = process_line_to_u64(&lines[2]);
-    let fourth_term_raw = process_line_to_u64(&lines[3]);
-    let operator: Vec<&str> = lines[4].split(' ').filter(|&c| c != "").collect();
-    let mut equations: Vec<Equation> = vec![];
-
-    if first_term_raw.len() != sec_term_raw.len() || third_term_raw.len() != operator.len() {
-        println!("Error: Vector length mismatch.");
-    } else {
-        let length = first_term_raw.len();
-        for i in 0..length {
-            equations.push(Equation {
-                terms: vec![
-                    first_term_raw[i],
-                    sec_term_raw[i],
-                    third_term_raw[i],
-                    fourth_term_raw[i],
-                ],
-                operator: operator[i].chars().next().unwrap(),
-            });
+// The answers again without the u64 limit when `big` is set, otherwise a
+// hint to set it for any part that only fails because of that limit.
+fn big_totals(file_path: &str, big: bool, format: Format) -> Result<(), Box<dyn Error>> {
+    let worksheet = day6::parse(&fs::read_to_string(file_path)?)?;
+    for (part, order) in [(1, ReadingOrder::Rows), (2, ReadingOrder::Columns)] {
+        let total = day6::big_total(&worksheet, order);
+        if big {
+            print_extra(format, format!("Part {part} big total: {total}"));
+        } else if day6::total(&worksheet, order).is_err() {
+            print_extra(
+                format,
+                format!("Part {part} leaves the u64 range, rerun with --big."),
+            );
         }
     }
-    equations
-}
-
-fn process_line_to_u64(line: &str) -> Vec<u64> {
-    line.split(' ')
-        .filter(|&c| c != "")
-        .map(|x| x.parse::<u64>().unwrap())
-        .collect()
-}
-
-#[derive(Debug)]
-struct Equation {
-    terms: Vec<u64>,
-    operator: char,
+    Ok(())
 }
 
-impl Equation {
-    fn build(raw: &Vec<String>) -> Equation {
-        let mut terms: Vec<u64> = vec![];
-        let mut first_term = raw[0].chars();
-        let operator = first_term.next_back().unwrap();
-        let t = first_term.as_str();
-        terms.push(t.trim().parse::<u64>().unwrap());
-        for r in &raw[1..] {
-            terms.push(r.trim().parse::<u64>().unwrap());
-        }
-        Equation {
-            terms: terms,
-            operator: operator,
-        }
-    }
-    fn result(&self) -> u64 {
-        let mut result = 0;
-        match self.operator {
-            '*' => {
-                result = 1;
-                for i in &self.terms {
-                    result *= *i;
-                }
-            }
-            '+' => {
-                for i in &self.terms {
-                    result += *i;
-                }
-            }
-
-            _ => {
-                println!("Invalid operator");
-            }
-        }
-        // dbg!(&self);
-        // dbg!(result);
-        result
+fn parse_big(args: &[String]) -> Result<bool, &'static str> {
+    match args.get(2).map(|s| s.as_str()) {
+        None => Ok(false),
+        Some("--big") => Ok(true),
+        Some(_) => Err("Unknown option, expected --big."),
     }
 }
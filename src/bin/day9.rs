@@ -0,0 +1,79 @@
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::process;
+
+use advent_of_code::day9;
+use advent_of_code::runner::{self, Format};
+use advent_of_code::solution;
+use advent_of_code::util::{parse_args, print_extra};
+
+// Pictures wider or taller than this are scaled down.
+const MAX_CELLS: usize = 100;
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let (file_path, format) = parse_args(&mut args).unwrap_or_else(|err| {
+        println!("Problem parsing arguments: {err}");
+        process::exit(1);
+    });
+    let export = parse_export(&args).unwrap_or_else(|err| {
+        println!("Problem parsing arguments: {err}");
+        process::exit(1);
+    });
+
+    let day = solution::find(9).expect("day 9 is registered");
+    if !runner::print_day(day, &file_path, format, day.run) {
+        process::exit(1);
+    }
+    if let Some(export) = export
+        && let Err(e) = run(&file_path, export, format)
+    {
+        println!("Application error: {e}");
+        process::exit(1);
+    }
+}
+
+fn run(file_path: &str, export: Export, format: Format) -> Result<(), Box<dyn Error>> {
+    let tiles = day9::parse(&fs::read_to_string(file_path)?)?;
+    let part1 = day9::best_rectangle(&tiles);
+    let part2 = day9::best_inside_rectangle(&tiles)?;
+    match export {
+        Export::Ascii => {
+            let pictures = [
+                day9::render_ascii(&tiles, part1, false, MAX_CELLS),
+                day9::render_ascii(&tiles, part2, true, MAX_CELLS),
+            ];
+            for (part, picture) in [1, 2].into_iter().zip(pictures) {
+                print_extra(format, format!("Part {part}:\n{}", picture.trim_end()));
+            }
+        }
+        Export::Svg(path) => {
+            fs::write(&path, day9::render_svg(&tiles, part1, part2))?;
+            print_extra(format, format!("Wrote {path}"));
+        }
+    }
+    for (part, rect) in [(1, part1), (2, part2)] {
+        if let Some(r) = rect {
+            let corners = format!("{},{} to {},{}", r.min.x, r.min.y, r.max.x, r.max.y);
+            print_extra(format, format!("Part {part} rectangle: {corners}"));
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Export {
+    Ascii,
+    Svg(String),
+}
+
+fn parse_export(args: &[String]) -> Result<Option<Export>, &'static str> {
+    match (args.get(2).map(|s| s.as_str()), args.get(3)) {
+        (None, _) => Ok(None),
+        (Some("--ascii"), _) => Ok(Some(Export::Ascii)),
+        (Some("--svg"), Some(path)) => Ok(Some(Export::Svg(path.clone()))),
+        (Some("--svg"), None) => Err("--svg expects an output file."),
+        (Some(_), _) => Err("Unknown export mode, expected --ascii or --svg <file>."),
+    }
+}
@@ -0,0 +1,137 @@
+use crate::num::Integer;
+
+/// Maps the distinct values on one axis to compact cell indices and back.
+/// With gaps, the run of values strictly between two neighbours gets a cell
+/// of its own, so every coordinate from the smallest value to the largest
+/// falls in exactly one cell.
+#[derive(Debug, Clone)]
+pub struct Compressed<T> {
+    // Inclusive range of coordinates covered by each cell, in order.
+    cells: Vec<(T, T)>,
+}
+
+impl<T: Integer> Compressed<T> {
+    pub fn new(values: impl IntoIterator<Item = T>, gaps: bool) -> Compressed<T> {
+        let mut values: Vec<T> = values.into_iter().collect();
+        values.sort_unstable();
+        values.dedup();
+        let mut cells = vec![];
+        for (i, v) in values.iter().enumerate() {
+            cells.push((*v, *v));
+            if gaps
+                && let Some(next) = values.get(i + 1)
+                && *next - *v > T::ONE
+            {
+                cells.push((*v + T::ONE, *next - T::ONE));
+            }
+        }
+        Compressed { cells }
+    }
+
+    /// Number of cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The cell holding `value`, `None` if no cell covers it.
+    pub fn index(&self, value: T) -> Option<usize> {
+        let i = self.cells.partition_point(|(low, _)| *low <= value);
+        let i = i.checked_sub(1)?;
+        (value <= self.cells[i].1).then_some(i)
+    }
+
+    /// The inclusive range of coordinates in cell `i`.
+    pub fn span(&self, i: usize) -> (T, T) {
+        self.cells[i]
+    }
+
+    /// How many coordinates cell `i` covers.
+    pub fn width(&self, i: usize) -> T {
+        let (low, high) = self.cells[i];
+        high - low + T::ONE
+    }
+}
+
+/// Sums of a grid of values over any rectangle of cells in O(1), after an
+/// O(width * height) build.
+#[derive(Debug, Clone)]
+pub struct SummedArea<T> {
+    width: usize,
+    // sums[y * (width + 1) + x] is the total of every cell left of x and
+    // above y, with an extra zero row and column at the start.
+    sums: Vec<T>,
+}
+
+impl<T: Integer> SummedArea<T> {
+    pub fn build(width: usize, height: usize, mut cell: impl FnMut(usize, usize) -> T) -> Self {
+        let stride = width + 1;
+        let mut sums = vec![T::ZERO; stride * (height + 1)];
+        for y in 0..height {
+            for x in 0..width {
+                sums[(y + 1) * stride + x + 1] =
+                    cell(x, y) + sums[y * stride + x + 1] + sums[(y + 1) * stride + x]
+                        - sums[y * stride + x];
+            }
+        }
+        SummedArea { width, sums }
+    }
+
+    /// Total over cells `x0..=x1` and `y0..=y1`.
+    pub fn sum(&self, (x0, y0): (usize, usize), (x1, y1): (usize, usize)) -> T {
+        let stride = self.width + 1;
+        let at = |x: usize, y: usize| self.sums[y * stride + x];
+        at(x1 + 1, y1 + 1) + at(x0, y0) - at(x0, y1 + 1) - at(x1 + 1, y0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::Rng;
+
+    #[test]
+    fn compressed_cells_and_gaps() {
+        let axis = Compressed::new([10, 2, 3, 10, 7], true);
+        assert_eq!(axis.len(), 6);
+        let spans: Vec<(i32, i32)> = (0..axis.len()).map(|i| axis.span(i)).collect();
+        assert_eq!(spans, [(2, 2), (3, 3), (4, 6), (7, 7), (8, 9), (10, 10)]);
+        assert_eq!(axis.index(5), Some(2));
+        assert_eq!(axis.index(7), Some(3));
+        assert_eq!(axis.index(1), None);
+        assert_eq!(axis.index(11), None);
+        assert_eq!(axis.width(4), 2);
+
+        let tight = Compressed::new([10u64, 2, 7], false);
+        assert_eq!(tight.len(), 3);
+        assert_eq!(tight.index(7), Some(1));
+        assert_eq!(tight.index(8), None);
+        assert!(Compressed::<u8>::new([], true).is_empty());
+    }
+
+    #[test]
+    fn summed_area_matches_brute_force() {
+        let mut rng = Rng::new(6);
+        for _ in 0..50 {
+            let (w, h) = (rng.range(1, 8) as usize, rng.range(1, 8) as usize);
+            let grid: Vec<Vec<i64>> = (0..h)
+                .map(|_| (0..w).map(|_| rng.range(0, 20) as i64 - 10).collect())
+                .collect();
+            let table = SummedArea::build(w, h, |x, y| grid[y][x]);
+            for _ in 0..20 {
+                let (xa, xb) = (rng.range(0, w as u64 - 1), rng.range(0, w as u64 - 1));
+                let (ya, yb) = (rng.range(0, h as u64 - 1), rng.range(0, h as u64 - 1));
+                let (x0, x1) = (xa.min(xb) as usize, xa.max(xb) as usize);
+                let (y0, y1) = (ya.min(yb) as usize, ya.max(yb) as usize);
+                let brute: i64 = grid[y0..=y1]
+                    .iter()
+                    .map(|r| r[x0..=x1].iter().sum::<i64>())
+                    .sum();
+                assert_eq!(table.sum((x0, y0), (x1, y1)), brute);
+            }
+        }
+    }
+}
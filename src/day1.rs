@@ -0,0 +1,373 @@
+use std::error::Error;
+use std::fmt::Write;
+
+use crate::num;
+use crate::parse::ParseError;
+use crate::runner::json_string;
+use crate::solution::Solution;
+
+const DIAL_SIZE: i32 = 100;
+pub const START_POSITION: i32 = 50;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Rotation>;
+
+    fn parse(raw: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse(raw)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(solve(input).0.to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(solve(input).1.to_string())
+    }
+}
+
+/// Returns how many rotations leave the dial on zero (part 1) and how many
+/// times the dial points at zero at any click (part 2).
+pub fn solve(rotations: &[Rotation]) -> (i32, i32) {
+    let mut pos = START_POSITION;
+    let mut landings = 0;
+    let mut count = 0;
+    for instruction in rotations {
+        let (new_pos, passes) = rotate_lock(pos, instruction.signed());
+        count += passes;
+        pos = new_pos;
+        if pos == 0 {
+            landings += 1;
+        }
+    }
+    (landings, count)
+}
+
+/// Turns the dial from `pos`, returning where it stops and how many clicks
+/// along the way (including the last) point at zero.
+pub fn rotate_lock(pos: i32, rotate: i32) -> (i32, i32) {
+    let end = pos + rotate;
+    // Zero is shown once per multiple of the dial size crossed, counting the
+    // end of the turn but not its start.
+    let count = if rotate >= 0 {
+        num::div_mod(end, DIAL_SIZE).0 - num::div_mod(pos, DIAL_SIZE).0
+    } else {
+        num::div_mod(pos - 1, DIAL_SIZE).0 - num::div_mod(end - 1, DIAL_SIZE).0
+    };
+    (num::div_mod(end, DIAL_SIZE).1, count)
+}
+
+/// What one rotation did to the dial.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TraceStep {
+    /// Position of the rotation in the input, from 0.
+    pub index: usize,
+    pub raw: String,
+    pub direction: Direction,
+    pub magnitude: i32,
+    pub before: i32,
+    pub after: i32,
+    /// Whether the dial stopped on zero.
+    pub lands_on_zero: bool,
+    /// Clicks that pointed at zero, the last one included.
+    pub zero_passes: i32,
+}
+
+/// Replays the rotations in `contents` from the start position, one step
+/// per rotation.
+pub fn trace(contents: &str, skip_blank: bool) -> Result<Vec<TraceStep>, ParseError> {
+    let mut pos = START_POSITION;
+    let mut steps = vec![];
+    for (index, (raw, rotation)) in instructions(contents, skip_blank)?.into_iter().enumerate() {
+        let (after, zero_passes) = rotate_lock(pos, rotation.signed());
+        steps.push(TraceStep {
+            index,
+            raw: raw.to_string(),
+            direction: rotation.dir,
+            magnitude: rotation.amount,
+            before: pos,
+            after,
+            lands_on_zero: after == 0,
+            zero_passes,
+        });
+        pos = after;
+    }
+    Ok(steps)
+}
+
+const TRACE_HEADER: &str = "index,raw,direction,magnitude,before,after,lands_on_zero,zero_passes";
+
+/// The trace as CSV with a header row.
+pub fn trace_csv(steps: &[TraceStep]) -> String {
+    let mut out = format!("{TRACE_HEADER}\n");
+    for s in steps {
+        // Quote the raw text in case a malformed line holds a comma or quote.
+        let raw = format!("\"{}\"", s.raw.replace('"', "\"\""));
+        writeln!(
+            out,
+            "{},{raw},{},{},{},{},{},{}",
+            s.index,
+            s.direction.letter(),
+            s.magnitude,
+            s.before,
+            s.after,
+            s.lands_on_zero,
+            s.zero_passes
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// The trace as one JSON object per line, with the same fields as the CSV.
+pub fn trace_json_lines(steps: &[TraceStep]) -> String {
+    let mut out = String::new();
+    for s in steps {
+        writeln!(
+            out,
+            "{{\"index\":{},\"raw\":{},\"direction\":\"{}\",\"magnitude\":{},\"before\":{},\"after\":{},\"lands_on_zero\":{},\"zero_passes\":{}}}",
+            s.index,
+            json_string(&s.raw),
+            s.direction.letter(),
+            s.magnitude,
+            s.before,
+            s.after,
+            s.lands_on_zero,
+            s.zero_passes
+        )
+        .unwrap();
+    }
+    out
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub fn letter(&self) -> char {
+        match self {
+            Direction::Left => 'L',
+            Direction::Right => 'R',
+        }
+    }
+}
+
+// Anything larger could overflow when added to a dial position.
+const MAX_AMOUNT: i32 = i32::MAX - DIAL_SIZE;
+
+/// One line of the input, such as `L68`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Rotation {
+    pub dir: Direction,
+    pub amount: i32,
+}
+
+impl Rotation {
+    pub fn build(raw: &str) -> Result<Rotation, &'static str> {
+        let mut chars = raw.chars();
+        let dir = match chars.next() {
+            Some('L') => Direction::Left,
+            Some('R') => Direction::Right,
+            Some(_) => return Err("Unknown direction, expected 'L' or 'R'"),
+            None => return Err("Empty line, expected a rotation"),
+        };
+        let amount = chars.as_str();
+        if amount.is_empty() || !amount.bytes().all(|b| b.is_ascii_digit()) {
+            return Err("Invalid rotation amount");
+        }
+        let amount = amount
+            .parse::<i32>()
+            .ok()
+            .filter(|a| *a <= MAX_AMOUNT)
+            .ok_or("Rotation amount is too large")?;
+        Ok(Rotation { dir, amount })
+    }
+
+    /// Clicks to turn the dial, negative for left.
+    pub fn signed(&self) -> i32 {
+        match self.dir {
+            Direction::Left => -self.amount,
+            Direction::Right => self.amount,
+        }
+    }
+}
+
+/// One rotation per line. Blank lines are an error.
+pub fn parse(contents: &str) -> Result<Vec<Rotation>, ParseError> {
+    parse_with(contents, false)
+}
+
+/// One rotation per line, ignoring blank lines when `skip_blank` is set.
+pub fn parse_with(contents: &str, skip_blank: bool) -> Result<Vec<Rotation>, ParseError> {
+    Ok(instructions(contents, skip_blank)?
+        .into_iter()
+        .map(|(_, r)| r)
+        .collect())
+}
+
+// Each rotation with the line it came from.
+fn instructions(contents: &str, skip_blank: bool) -> Result<Vec<(&str, Rotation)>, ParseError> {
+    contents
+        .lines()
+        .filter(|line| !skip_blank || !line.trim().is_empty())
+        .map(|line| {
+            let rotation =
+                Rotation::build(line.trim_end()).map_err(|e| ParseError::at(contents, line, e))?;
+            Ok((line, rotation))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::Rng;
+
+    // Turns the dial one click at a time, counting every time it shows zero.
+    fn brute_rotate(pos: i32, rotate: i32) -> (i32, i32) {
+        let step = rotate.signum();
+        let mut current = pos;
+        let mut count = 0;
+        for _ in 0..rotate.abs() {
+            current = (current + step).rem_euclid(100);
+            if current == 0 {
+                count += 1;
+            }
+        }
+        (current, count)
+    }
+
+    #[test]
+    fn rotate_lock_example() {
+        let mut pos = 50;
+        let mut count = 0;
+        for r in [-68, -30, 48, -5, 60, -55, -1, -99, 14, -82] {
+            let (new_pos, passes) = rotate_lock(pos, r);
+            pos = new_pos;
+            count += passes;
+        }
+        assert_eq!(pos, 32);
+        assert_eq!(count, 6);
+    }
+
+    #[test]
+    fn solve_example() {
+        let rotations = parse("L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n").unwrap();
+        assert_eq!(solve(&rotations), (3, 6));
+    }
+
+    #[test]
+    fn rotate_lock_full_turns() {
+        assert_eq!(rotate_lock(50, 1000), (50, 10));
+        assert_eq!(rotate_lock(0, -100), (0, 1));
+        assert_eq!(rotate_lock(0, -1), (99, 0));
+    }
+
+    #[test]
+    fn rotate_lock_matches_brute_force() {
+        let mut rng = Rng::new(1);
+        for _ in 0..2000 {
+            let pos = rng.range(0, 99) as i32;
+            let rotate = rng.range(0, 600) as i32 - 300;
+            assert_eq!(
+                rotate_lock(pos, rotate),
+                brute_rotate(pos, rotate),
+                "{pos} by {rotate}"
+            );
+        }
+    }
+
+    #[test]
+    fn trace_example() {
+        let steps = trace("L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n", false).unwrap();
+        assert_eq!(steps.len(), 10);
+        assert_eq!(
+            steps[0],
+            TraceStep {
+                index: 0,
+                raw: "L68".to_string(),
+                direction: Direction::Left,
+                magnitude: 68,
+                before: 50,
+                after: 82,
+                lands_on_zero: false,
+                zero_passes: 1,
+            }
+        );
+        assert_eq!((steps[2].before, steps[2].after), (52, 0));
+        assert!(steps[2].lands_on_zero);
+        assert_eq!(steps.iter().filter(|s| s.lands_on_zero).count(), 3);
+        assert_eq!(steps.iter().map(|s| s.zero_passes).sum::<i32>(), 6);
+        assert_eq!(steps[9].after, 32);
+    }
+
+    #[test]
+    fn trace_exports() {
+        let steps = trace("L68\n\nR48\n", true).unwrap();
+        assert_eq!(
+            trace_csv(&steps),
+            "index,raw,direction,magnitude,before,after,lands_on_zero,zero_passes\n\
+             0,\"L68\",L,68,50,82,false,1\n\
+             1,\"R48\",R,48,82,30,false,1\n"
+        );
+        assert_eq!(
+            trace_json_lines(&steps[..1]),
+            "{\"index\":0,\"raw\":\"L68\",\"direction\":\"L\",\"magnitude\":68,\
+             \"before\":50,\"after\":82,\"lands_on_zero\":false,\"zero_passes\":1}\n"
+        );
+    }
+
+    #[test]
+    fn parse_input_signs_left_turns() {
+        let rotations = parse("L68\nR48\n").unwrap();
+        assert_eq!(
+            rotations,
+            vec![
+                Rotation {
+                    dir: Direction::Left,
+                    amount: 68
+                },
+                Rotation {
+                    dir: Direction::Right,
+                    amount: 48
+                },
+            ]
+        );
+        let signed: Vec<i32> = rotations.iter().map(|r| r.signed()).collect();
+        assert_eq!(signed, vec![-68, 48]);
+    }
+
+    #[test]
+    fn parse_rejects_bad_lines_with_location() {
+        let error = |raw| parse(raw).unwrap_err().to_string();
+        assert_eq!(
+            error("L1\nX5\n"),
+            "line 2, column 1: Unknown direction, expected 'L' or 'R'"
+        );
+        assert_eq!(
+            error("L1\n\nR2\n"),
+            "line 2, column 1: Empty line, expected a rotation"
+        );
+        assert_eq!(
+            error("R99999999999\n"),
+            "line 1, column 1: Rotation amount is too large"
+        );
+        assert_eq!(
+            error("L1\nR2\nR-3\n"),
+            "line 3, column 1: Invalid rotation amount"
+        );
+        assert!(parse("L\n").is_err());
+        assert!(Rotation::build(&format!("R{MAX_AMOUNT}")).is_ok());
+        assert!(Rotation::build(&format!("R{}", MAX_AMOUNT + 1)).is_err());
+    }
+
+    #[test]
+    fn parse_can_skip_blank_lines() {
+        assert_eq!(parse_with("L1\n\n  \nR2\n", true).unwrap().len(), 2);
+        assert!(parse_with("L1\n\nR2\n", false).is_err());
+    }
+}
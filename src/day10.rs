@@ -0,0 +1,307 @@
+use std::error::Error;
+
+use crate::graph;
+use crate::parse::{self as parsing, ParseError};
+use crate::solution::Solution;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Machine>;
+
+    fn parse(raw: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse(raw)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let mut total = 0;
+        for machine in input {
+            total += machine.min_presses()?;
+        }
+        Ok(total.to_string())
+    }
+
+    fn part2(_input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Err("Part 2 is not solved yet".into())
+    }
+}
+
+// Elimination tries every combination of free buttons, so cap them.
+const MAX_FREE_BUTTONS: usize = 24;
+// The breadth first search may visit every light state, so cap the lights.
+const MAX_SEARCH_LIGHTS: usize = 24;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Machine {
+    /// Number of indicator lights.
+    pub light_count: usize,
+    /// Target light state, bit `i` set when light `i` should be on.
+    pub lights: u64,
+    /// Lights toggled by each button, as indexes.
+    pub buttons: Vec<Vec<usize>>,
+    pub joltage: Vec<u64>,
+}
+
+impl Machine {
+    pub fn build(raw: &str) -> Result<Machine, &'static str> {
+        let mut light_count = 0;
+        let mut lights = 0;
+        let mut buttons = vec![];
+        let mut joltage = vec![];
+        for part in raw.split_whitespace() {
+            if let Some(diagram) = part.strip_prefix('[').and_then(|p| p.strip_suffix(']')) {
+                light_count = diagram.len();
+                if light_count > 64 {
+                    return Err("Machines with more than 64 lights are not supported");
+                }
+                for (i, c) in diagram.chars().enumerate() {
+                    match c {
+                        '#' => lights |= 1 << i,
+                        '.' => {}
+                        _ => return Err("Invalid indicator light, expected '.' or '#'"),
+                    }
+                }
+            } else if let Some(wiring) = part.strip_prefix('(').and_then(|p| p.strip_suffix(')')) {
+                let button = parse_list::<usize>(wiring)?;
+                if button.iter().any(|l| *l >= light_count) {
+                    return Err("Button toggles a light the machine does not have");
+                }
+                buttons.push(button);
+            } else if let Some(levels) = part.strip_prefix('{').and_then(|p| p.strip_suffix('}')) {
+                joltage = parse_list::<u64>(levels)?;
+            } else {
+                return Err("Unrecognised machine section");
+            }
+        }
+        if light_count == 0 {
+            return Err("Machine is missing its indicator light diagram");
+        }
+        // Presses are tracked as one bit per button.
+        if buttons.len() > 64 {
+            return Err("Machines with more than 64 buttons are not supported");
+        }
+        Ok(Machine {
+            light_count,
+            lights,
+            buttons,
+            joltage,
+        })
+    }
+
+    /// Bitmask of the lights a button toggles.
+    pub fn button_mask(&self, button: usize) -> u64 {
+        self.buttons[button].iter().fold(0, |mask, l| mask | 1 << l)
+    }
+
+    /// Fewest presses to reach the target lights. Pressing a button twice
+    /// cancels out, so this solves `A x = lights` over GF(2) and searches the
+    /// null space for the solution with the fewest buttons set.
+    pub fn min_presses(&self) -> Result<u32, &'static str> {
+        // One row per light: which buttons toggle it, and whether it must end on.
+        let mut rows: Vec<(u64, bool)> = (0..self.light_count)
+            .map(|light| {
+                let mut mask = 0;
+                for button in 0..self.buttons.len() {
+                    if self.buttons[button].contains(&light) {
+                        mask |= 1 << button;
+                    }
+                }
+                (mask, self.lights >> light & 1 == 1)
+            })
+            .collect();
+
+        let mut pivots: Vec<usize> = vec![];
+        let mut rank = 0;
+        for column in 0..self.buttons.len() {
+            let Some(found) = (rank..rows.len()).find(|r| rows[*r].0 >> column & 1 == 1) else {
+                continue;
+            };
+            rows.swap(rank, found);
+            let pivot = rows[rank];
+            for (r, row) in rows.iter_mut().enumerate() {
+                if r != rank && row.0 >> column & 1 == 1 {
+                    row.0 ^= pivot.0;
+                    row.1 ^= pivot.1;
+                }
+            }
+            pivots.push(column);
+            rank += 1;
+        }
+        if rows[rank..].iter().any(|(_, on)| *on) {
+            return Err("No combination of buttons reaches the target lights");
+        }
+
+        let free: Vec<usize> = (0..self.buttons.len())
+            .filter(|c| !pivots.contains(c))
+            .collect();
+        if free.len() > MAX_FREE_BUTTONS {
+            return Err("Too many free buttons to search");
+        }
+        let mut best = u32::MAX;
+        for choice in 0..1u64 << free.len() {
+            let mut presses: u64 = 0;
+            for (i, column) in free.iter().enumerate() {
+                if choice >> i & 1 == 1 {
+                    presses |= 1 << column;
+                }
+            }
+            for (row, column) in rows.iter().zip(&pivots) {
+                let parity = (row.0 & presses).count_ones() % 2 == 1;
+                if parity != row.1 {
+                    presses |= 1 << column;
+                }
+            }
+            best = best.min(presses.count_ones());
+        }
+        Ok(best)
+    }
+
+    /// Fewest presses to reach the target lights, found by a breadth first
+    /// search over light states with each button an XOR of its mask. Much
+    /// slower than `min_presses` but shares none of its reasoning, so the two
+    /// can check each other.
+    pub fn min_presses_search(&self) -> Result<u32, &'static str> {
+        if self.light_count > MAX_SEARCH_LIGHTS {
+            return Err("Too many lights to search");
+        }
+        let masks: Vec<u64> = (0..self.buttons.len())
+            .map(|b| self.button_mask(b))
+            .collect();
+        let states =
+            graph::implicit(|lights: &u64| masks.iter().map(|m| (lights ^ m, 1)).collect());
+        let path = graph::bfs(&states, 0, |lights| *lights == self.lights)
+            .ok_or("No combination of buttons reaches the target lights")?;
+        Ok(path.cost as u32)
+    }
+}
+
+/// A machine where the two solvers gave different answers.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Disagreement {
+    /// Index of the machine in the input.
+    pub machine: usize,
+    pub algebraic: Result<u32, &'static str>,
+    pub search: Result<u32, &'static str>,
+}
+
+/// Solves every machine with both `min_presses` and `min_presses_search`
+/// and returns those where the results differ.
+pub fn cross_check(machines: &[Machine]) -> Vec<Disagreement> {
+    machines
+        .iter()
+        .enumerate()
+        .map(|(i, m)| Disagreement {
+            machine: i,
+            algebraic: m.min_presses(),
+            search: m.min_presses_search(),
+        })
+        .filter(|d| d.algebraic != d.search)
+        .collect()
+}
+
+fn parse_list<T: std::str::FromStr>(raw: &str) -> Result<Vec<T>, &'static str> {
+    raw.split(',')
+        .map(|n| {
+            n.trim()
+                .parse::<T>()
+                .map_err(|_| "Invalid number in machine")
+        })
+        .collect()
+}
+
+pub fn parse(raw: &str) -> Result<Vec<Machine>, ParseError> {
+    parsing::lines(raw, Machine::build)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::Rng;
+
+    const EXAMPLE: &str = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
+";
+
+    #[test]
+    fn build_reads_every_section() {
+        let machine = Machine::build("[.##.] (3) (1,3) (2) {3,5,4,7}").unwrap();
+        assert_eq!(machine.light_count, 4);
+        assert_eq!(machine.lights, 0b0110);
+        assert_eq!(machine.buttons, vec![vec![3], vec![1, 3], vec![2]]);
+        assert_eq!(machine.joltage, vec![3, 5, 4, 7]);
+        assert_eq!(machine.button_mask(1), 0b1010);
+    }
+
+    #[test]
+    fn build_rejects_bad_machines() {
+        assert!(Machine::build("(0,1) {1}").is_err());
+        assert!(Machine::build("[.#] (2)").is_err());
+        assert!(Machine::build("[.x]").is_err());
+        let many_buttons = format!("[#] {}", "(0) ".repeat(65));
+        assert!(Machine::build(&many_buttons).is_err());
+        // One button per light, so the last button is bit 63 of a press set.
+        let buttons: Vec<String> = (0..64).map(|i| format!("({i})")).collect();
+        let max_buttons = format!("[{}#] {}", ".".repeat(63), buttons.join(" "));
+        let machine = Machine::build(&max_buttons).unwrap();
+        assert_eq!(machine.min_presses(), Ok(1));
+        assert!(machine.min_presses_search().is_err());
+    }
+
+    #[test]
+    fn min_presses_example() {
+        let machines = parse(EXAMPLE).unwrap();
+        let presses: Vec<u32> = machines.iter().map(|m| m.min_presses().unwrap()).collect();
+        assert_eq!(presses, vec![2, 3, 2]);
+    }
+
+    #[test]
+    fn min_presses_unreachable() {
+        assert!(Machine::build("[#.] (1)").unwrap().min_presses().is_err());
+    }
+
+    #[test]
+    fn search_matches_example() {
+        let machines = parse(EXAMPLE).unwrap();
+        let presses: Vec<u32> = machines
+            .iter()
+            .map(|m| m.min_presses_search().unwrap())
+            .collect();
+        assert_eq!(presses, vec![2, 3, 2]);
+        assert!(cross_check(&machines).is_empty());
+        assert!(
+            Machine::build("[#.] (1)")
+                .unwrap()
+                .min_presses_search()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn search_agrees_with_elimination() {
+        let mut rng = Rng::new(10);
+        let machines: Vec<Machine> = (0..200)
+            .map(|_| {
+                let light_count = rng.range(1, 10) as usize;
+                let buttons = (0..rng.range(1, 12))
+                    .map(|_| {
+                        let mut button: Vec<usize> =
+                            (0..light_count).filter(|_| rng.chance(1, 3)).collect();
+                        if button.is_empty() {
+                            button.push(rng.range(0, light_count as u64 - 1) as usize);
+                        }
+                        button
+                    })
+                    .collect();
+                Machine {
+                    light_count,
+                    lights: rng.next_u64() & ((1 << light_count) - 1),
+                    buttons,
+                    joltage: vec![],
+                }
+            })
+            .collect();
+        assert_eq!(cross_check(&machines), vec![]);
+        assert!(machines.iter().any(|m| m.min_presses().is_err()));
+    }
+}
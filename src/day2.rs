@@ -0,0 +1,225 @@
+use std::collections::HashSet;
+use std::error::Error;
+
+use crate::num;
+use crate::parse::{self as parsing, ParseError};
+use crate::solution::Solution;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<IDRange>;
+
+    fn parse(raw: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse(raw)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(solve(input)?.0.to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(solve(input)?.1.to_string())
+    }
+}
+
+pub fn parse(raw: &str) -> Result<Vec<IDRange>, ParseError> {
+    parsing::delimited(raw, ',', IDRange::build)
+}
+
+/// Sums the distinct ids made of a block repeated exactly twice (part 1) and
+/// the distinct ids made of a block repeated any number of times (part 2).
+pub fn solve(ranges: &[IDRange]) -> Result<(i64, i64), &'static str> {
+    solve_with(ranges, get_sillyids)
+}
+
+/// Same as `solve`, but compares the digits of each id as text.
+pub fn solve_by_strings(ranges: &[IDRange]) -> Result<(i64, i64), &'static str> {
+    solve_with(ranges, get_sillyids_by_string)
+}
+
+fn solve_with(
+    ranges: &[IDRange],
+    find: fn(&IDRange) -> Result<Vec<i64>, &'static str>,
+) -> Result<(i64, i64), &'static str> {
+    let mut silly_ids: HashSet<i64> = HashSet::new();
+    for range in ranges {
+        silly_ids.extend(find(range)?);
+    }
+    let doubled_sum = silly_ids.iter().filter(|id| is_doubled(**id)).sum();
+    let silly_sum = silly_ids.iter().sum();
+    Ok((doubled_sum, silly_sum))
+}
+
+fn is_doubled(id: i64) -> bool {
+    let len = num::digit_count(id);
+    len.is_multiple_of(2) && is_repeated(id, len, len / 2)
+}
+
+// A `len` digit id is a `block` digit pattern repeated exactly when the
+// repunit with `block` digit places divides it, e.g. 123123 = 123 * 1001.
+fn is_repeated(id: i64, len: u32, block: u32) -> bool {
+    id % num::repunit(len / block, num::pow(10, block)) == 0
+}
+
+#[derive(Debug)]
+pub struct IDRange {
+    pub low_value: i64,
+    pub high_value: i64,
+}
+
+impl IDRange {
+    pub fn build(raw_range: &str) -> Result<IDRange, &'static str> {
+        let ranges: Vec<&str> = raw_range.split('-').collect();
+        if ranges.len() < 2 {
+            return Err("Not a valid id range string: Not enough IDs");
+        }
+        let low_value = ranges[0]
+            .trim()
+            .parse::<i64>()
+            .map_err(|_| "Error getting int from str: low_value")?;
+        let high_value = ranges[1]
+            .trim()
+            .parse::<i64>()
+            .map_err(|_| "Error getting int from str: high_value")?;
+
+        if high_value < low_value {
+            return Err("Invalid range low_value is larger than high value.");
+        }
+        Ok(IDRange {
+            low_value,
+            high_value,
+        })
+    }
+}
+
+pub fn get_sillyids(range: &IDRange) -> Result<Vec<i64>, &'static str> {
+    let mut silly_ids: Vec<i64> = Vec::new();
+
+    for i in range.low_value..=range.high_value {
+        let len = num::digit_count(i);
+        for n in valid_pattern_factor(len as usize) {
+            if n == len as usize {
+                continue;
+            }
+            if is_repeated(i, len, n as u32) {
+                silly_ids.push(i);
+                break;
+            }
+        }
+    }
+    Ok(silly_ids)
+}
+
+/// Same ids as `get_sillyids`, found by cutting each id's digits into equal
+/// blocks and comparing them.
+pub fn get_sillyids_by_string(range: &IDRange) -> Result<Vec<i64>, &'static str> {
+    let mut silly_ids: Vec<i64> = Vec::new();
+    for i in range.low_value..=range.high_value {
+        let id = i.to_string();
+        let silly = valid_pattern_factor(id.len())
+            .into_iter()
+            .filter(|n| *n != id.len())
+            .any(|n| {
+                let blocks = split_every(&id, n);
+                blocks.iter().all(|b| *b == blocks[0])
+            });
+        if silly {
+            silly_ids.push(i);
+        }
+    }
+    Ok(silly_ids)
+}
+
+pub fn split_every(value: &str, n: usize) -> Vec<&str> {
+    let number_of_slices = value.len() / n;
+    let mut r_vector: Vec<&str> = Vec::new();
+    for i in 0..number_of_slices {
+        let s = &value[i * n..(i + 1) * n];
+        r_vector.push(s);
+    }
+    r_vector
+}
+
+/// The block lengths a `number` digit id could repeat, largest first.
+pub fn valid_pattern_factor(number: usize) -> Vec<usize> {
+    let mut result = num::divisors(number);
+    if number > 1 {
+        result.pop();
+    }
+    result.reverse();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::Rng;
+
+    // An id is silly when it is some block of digits repeated at least twice.
+    fn brute_is_silly(id: i64) -> bool {
+        let s = id.to_string();
+        (1..s.len()).any(|n| s.len().is_multiple_of(n) && s == s[..n].repeat(s.len() / n))
+    }
+
+    #[test]
+    fn split_every_chunks_evenly() {
+        assert_eq!(split_every("123123", 3), vec!["123", "123"]);
+        assert_eq!(split_every("121212", 2), vec!["12", "12", "12"]);
+        assert_eq!(split_every("1234567", 3), vec!["123", "456"]);
+    }
+
+    #[test]
+    fn valid_pattern_factor_largest_first() {
+        assert_eq!(valid_pattern_factor(6), vec![3, 2, 1]);
+        assert_eq!(valid_pattern_factor(7), vec![1]);
+        assert_eq!(valid_pattern_factor(10), vec![5, 2, 1]);
+    }
+
+    #[test]
+    fn build_rejects_bad_ranges() {
+        assert!(IDRange::build("11-22").is_ok());
+        assert!(IDRange::build("22-11").is_err());
+        assert!(IDRange::build("11").is_err());
+        assert!(IDRange::build("a-22").is_err());
+    }
+
+    #[test]
+    fn silly_ids_in_example_ranges() {
+        let ids = |raw| get_sillyids(&IDRange::build(raw).unwrap()).unwrap();
+        assert_eq!(ids("11-22"), vec![11, 22]);
+        assert_eq!(ids("95-115"), vec![99, 111]);
+        assert_eq!(ids("998-1012"), vec![999, 1010]);
+        assert_eq!(ids("1698522-1698528"), vec![]);
+        let top = IDRange {
+            low_value: i64::MAX - 1,
+            high_value: i64::MAX,
+        };
+        assert_eq!(get_sillyids(&top), Ok(vec![]));
+        assert_eq!(get_sillyids_by_string(&top), Ok(vec![]));
+    }
+
+    #[test]
+    fn solve_example() {
+        let ranges = parse(
+            "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,\
+             1698522-1698528,446443-446449,38593856-38593862,565653-565659,\
+             824824821-824824827,2121212118-2121212124\n",
+        )
+        .unwrap();
+        assert_eq!(solve(&ranges), Ok((1227775554, 4174379265)));
+    }
+
+    #[test]
+    fn silly_ids_match_brute_force() {
+        let mut rng = Rng::new(2);
+        for _ in 0..200 {
+            let low = rng.range(1, 200_000) as i64;
+            let high = low + rng.range(0, 2_000) as i64;
+            let range = IDRange::build(&format!("{low}-{high}")).unwrap();
+            let expected: Vec<i64> = (low..=high).filter(|i| brute_is_silly(*i)).collect();
+            assert_eq!(get_sillyids(&range).unwrap(), expected, "{low}-{high}");
+            assert_eq!(get_sillyids_by_string(&range).unwrap(), expected);
+        }
+    }
+}
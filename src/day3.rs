@@ -0,0 +1,164 @@
+use std::error::Error;
+
+use crate::num;
+use crate::parse::{self as parsing, ParseError};
+use crate::solution::Solution;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<BatteryBank>;
+
+    fn parse(raw: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse(raw)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(total_joltage(input, 2)?.to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(total_joltage(input, 12)?.to_string())
+    }
+}
+
+pub fn parse(raw: &str) -> Result<Vec<BatteryBank>, ParseError> {
+    parsing::lines(raw, BatteryBank::build)
+}
+
+/// Sums the largest joltage each bank can make from `digits` batteries.
+pub fn total_joltage(banks: &[BatteryBank], digits: u32) -> Result<u64, &'static str> {
+    let mut total_joltage: u64 = 0;
+    for bank in banks {
+        total_joltage += bank.max_joltage(digits)?;
+    }
+    Ok(total_joltage)
+}
+
+#[derive(Debug)]
+pub struct BatteryBank {
+    pub batteries: Vec<u32>,
+}
+
+impl BatteryBank {
+    pub fn build(raw: &str) -> Result<BatteryBank, &'static str> {
+        let mut r_vector: Vec<u32> = Vec::new();
+        for c in raw.trim_end().chars() {
+            let res = c.to_digit(10);
+            match res {
+                Some(num) => {
+                    r_vector.push(num);
+                }
+                None => {
+                    return Err("No digit given");
+                }
+            }
+        }
+        if r_vector.is_empty() {
+            return Err("Bank has no batteries");
+        }
+        Ok(BatteryBank {
+            batteries: r_vector,
+        })
+    }
+
+    /// The largest number made by keeping `digits` batteries in order, an
+    /// error when the bank has fewer batteries than that.
+    pub fn max_joltage(&self, digits: u32) -> Result<u64, &'static str> {
+        if self.batteries.len() < digits as usize {
+            return Err("Bank has fewer batteries than digits requested");
+        }
+        let mut chosen: Vec<u8> = vec![];
+        let mut prev_index: i32 = -1;
+
+        for i in (0..digits).rev() {
+            let digit = BatteryBank::max_digit(
+                &self.batteries[(prev_index + 1) as usize..self.batteries.len() - i as usize],
+                (prev_index + 1) as usize,
+            );
+            prev_index = digit.0 as i32;
+            chosen.push(digit.1 as u8);
+        }
+
+        Ok(num::from_digits(chosen))
+    }
+
+    fn max_digit(batteries: &[u32], start_index: usize) -> (usize, u32) {
+        let mut current_max = (start_index, batteries[0]);
+        // println!("Starting max: ({}, {})", current_max.0, current_max.1);
+        for (i, j) in batteries[1..].iter().enumerate() {
+            let index = i + 1 + start_index;
+            if *j > current_max.1 {
+                current_max = (index, *j);
+                // println!("New max: ({}, {})", current_max.0, current_max.1);
+            }
+            if *j == 9 {
+                // println!("Found a 9");
+                break;
+            }
+        }
+        current_max
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::Rng;
+
+    // Tries every way of keeping `digits` batteries in order.
+    fn brute_max_joltage(batteries: &[u32], digits: usize) -> u64 {
+        if digits == 0 {
+            return 0;
+        }
+        let mut best = 0;
+        for i in 0..=batteries.len() - digits {
+            let rest = brute_max_joltage(&batteries[i + 1..], digits - 1);
+            let value = u64::from(batteries[i]) * 10u64.pow(digits as u32 - 1) + rest;
+            best = best.max(value);
+        }
+        best
+    }
+
+    #[test]
+    fn max_joltage_example() {
+        let bank = |raw| BatteryBank::build(raw).unwrap();
+        assert_eq!(bank("987654321111111").max_joltage(2), Ok(98));
+        assert_eq!(bank("811111111111119").max_joltage(2), Ok(89));
+        assert_eq!(bank("234234234234278").max_joltage(2), Ok(78));
+        assert_eq!(bank("818181911112111").max_joltage(2), Ok(92));
+        assert_eq!(bank("987654321111111").max_joltage(12), Ok(987654321111));
+        assert_eq!(bank("234234234234278").max_joltage(12), Ok(434234234278));
+    }
+
+    #[test]
+    fn short_banks_and_bad_lines_are_errors() {
+        let bank = BatteryBank::build("12345").unwrap();
+        assert!(bank.max_joltage(12).is_err());
+        assert_eq!(bank.max_joltage(5), Ok(12345));
+        assert!(BatteryBank::build("").is_err());
+        assert_eq!(parse("12\n\n34\n").unwrap().len(), 2);
+        assert_eq!(
+            parse("12\n3x\n").unwrap_err().to_string(),
+            "line 2, column 1: No digit given"
+        );
+    }
+
+    #[test]
+    fn max_joltage_matches_brute_force() {
+        let mut rng = Rng::new(3);
+        for _ in 0..300 {
+            let len = rng.range(1, 12) as usize;
+            let batteries: Vec<u32> = (0..len).map(|_| rng.range(1, 9) as u32).collect();
+            let digits = rng.range(1, len as u64) as u32;
+            let bank = BatteryBank {
+                batteries: batteries.clone(),
+            };
+            assert_eq!(
+                bank.max_joltage(digits),
+                Ok(brute_max_joltage(&batteries, digits as usize)),
+                "{batteries:?} keeping {digits}"
+            );
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::parse::{self as parsing, ParseError};
+use crate::solution::Solution;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = FloorGrid;
+
+    fn parse(raw: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse(raw)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(input.clone().remove_rolls().to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(remove_all(&mut input.clone()).to_string())
+    }
+}
+
+// -1 for every empty space add 1 to every square around a roll then count the squares with less than 4
+pub fn parse(raw: &str) -> Result<FloorGrid, ParseError> {
+    FloorGrid::build(raw)
+}
+
+/// Keeps removing accessible rolls until none are left, returning the total.
+pub fn remove_all(floor_grid: &mut FloorGrid) -> i32 {
+    let mut removed_rolls = floor_grid.remove_rolls();
+    let mut removed_count = removed_rolls;
+    while removed_rolls != 0 {
+        removed_rolls = floor_grid.remove_rolls();
+        removed_count += removed_rolls;
+        // println!("Remove {removed_rolls} roll of paper");
+        // dbg!(&floor_grid);
+    }
+    removed_count
+}
+
+#[derive(Clone)]
+pub struct FloorGrid {
+    warehouse: Vec<Vec<i32>>,
+    width: i32,
+    height: i32,
+}
+
+impl FloorGrid {
+    pub fn build(raw: &str) -> Result<FloorGrid, ParseError> {
+        let rolls = parsing::grid(raw, |c| match c {
+            '@' => Ok(true),
+            '.' => Ok(false),
+            _ => Err("Invalid floor cell, expected '.' or '@'"),
+        })?;
+        let mut grid = FloorGrid {
+            width: rolls[0].len() as i32,
+            height: rolls.len() as i32,
+            warehouse: rolls
+                .iter()
+                .map(|row| row.iter().map(|roll| if *roll { 0 } else { -1 }).collect())
+                .collect(),
+        };
+        grid.recalculate();
+        Ok(grid)
+    }
+
+    fn recalculate(&mut self) {
+        let mut new_warehouse = vec![vec![0; self.width as usize]; self.height as usize];
+        for (row, value) in &mut self.warehouse.iter().enumerate() {
+            for (column, roll) in &mut value.iter().enumerate() {
+                match roll {
+                    n if *n < 0 => {
+                        new_warehouse[row][column] = -1;
+                    }
+                    _ => {
+                        for r in row as i32 - 1..=row as i32 + 1 {
+                            if r >= 0 && r < self.height {
+                                for c in column as i32 - 1..=column as i32 + 1 {
+                                    if c >= 0 && c < self.width {
+                                        if r == row as i32 && c == column as i32 {
+                                            continue;
+                                        }
+                                        if new_warehouse[r as usize][c as usize] >= 0 {
+                                            new_warehouse[r as usize][c as usize] += 1;
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        self.warehouse = new_warehouse;
+    }
+
+    pub fn remove_rolls(&mut self) -> i32 {
+        let mut count = 0;
+        for row in &mut self.warehouse {
+            for value in row {
+                if *value < 4 && *value >= 0 {
+                    *value = -1;
+                    count += 1;
+                }
+            }
+        }
+        self.recalculate();
+        count
+    }
+}
+
+impl fmt::Debug for FloorGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Warehouse: [")?;
+        for line in &self.warehouse {
+            let mut c_line = String::new();
+            for n in line {
+                match n {
+                    -1 => c_line.push('.'),
+                    0..4 => c_line.push('x'),
+                    _ => c_line.push('@'),
+                }
+            }
+            writeln!(f, "{:?}", c_line)?;
+        }
+        write!(f, "]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::Rng;
+
+    const EXAMPLE: &str = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.
+";
+
+    // Removes every roll with fewer than four neighbouring rolls at once by
+    // counting neighbours directly on the character grid.
+    fn brute_remove(grid: &mut [Vec<char>]) -> i32 {
+        let height = grid.len() as i32;
+        let width = grid[0].len() as i32;
+        let mut removable = vec![];
+        for r in 0..height {
+            for c in 0..width {
+                if grid[r as usize][c as usize] != '@' {
+                    continue;
+                }
+                let mut neighbours = 0;
+                for dr in -1..=1 {
+                    for dc in -1..=1 {
+                        let (nr, nc) = (r + dr, c + dc);
+                        if (dr, dc) != (0, 0)
+                            && (0..height).contains(&nr)
+                            && (0..width).contains(&nc)
+                            && grid[nr as usize][nc as usize] == '@'
+                        {
+                            neighbours += 1;
+                        }
+                    }
+                }
+                if neighbours < 4 {
+                    removable.push((r as usize, c as usize));
+                }
+            }
+        }
+        for (r, c) in &removable {
+            grid[*r][*c] = '.';
+        }
+        removable.len() as i32
+    }
+
+    #[test]
+    fn remove_rolls_example() {
+        let mut grid = parse(EXAMPLE).unwrap();
+        assert_eq!(grid.remove_rolls(), 13);
+        assert_eq!(remove_all(&mut grid), 43 - 13);
+    }
+
+    #[test]
+    fn build_rejects_bad_grids() {
+        let error = |raw| parse(raw).unwrap_err().to_string();
+        assert_eq!(
+            error("@.@\n@.\n"),
+            "line 2, column 1: row is 2 wide, expected 3"
+        );
+        assert_eq!(
+            error("@.\n@.@\n"),
+            "line 2, column 1: row is 3 wide, expected 2"
+        );
+        assert_eq!(
+            error("@.\n.x\n"),
+            "line 2, column 2: Invalid floor cell, expected '.' or '@'"
+        );
+        assert!(parse("\n").is_err());
+    }
+
+    #[test]
+    fn remove_rolls_matches_brute_force() {
+        let mut rng = Rng::new(4);
+        for _ in 0..100 {
+            let height = rng.range(1, 12) as usize;
+            let width = rng.range(1, 12) as usize;
+            let mut chars: Vec<Vec<char>> = (0..height)
+                .map(|_| {
+                    (0..width)
+                        .map(|_| if rng.chance(2, 3) { '@' } else { '.' })
+                        .collect()
+                })
+                .collect();
+            let raw: String = chars
+                .iter()
+                .map(|row| row.iter().collect::<String>() + "\n")
+                .collect();
+            let mut grid = FloorGrid::build(&raw).unwrap();
+            loop {
+                let expected = brute_remove(&mut chars);
+                assert_eq!(grid.remove_rolls(), expected, "{raw}");
+                if expected == 0 {
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use std::error::Error;
+
+use crate::parse::{self as parsing, ParseError};
+use crate::solution::Solution;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Inventory;
+
+    fn parse(raw: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse(raw)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(fresh_stock(input).to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(fresh_ids(input).to_string())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Inventory {
+    pub ranges: Vec<FreshRange>,
+    pub stock: Vec<u64>,
+}
+
+/// The fresh ranges, a blank line, then the stock ids.
+pub fn parse(contents: &str) -> Result<Inventory, ParseError> {
+    let sections = parsing::sections(contents);
+    if sections.len() > 2 {
+        let extra = sections[2];
+        return Err(ParseError::at(
+            contents,
+            extra,
+            "Expected only ranges and stock",
+        ));
+    }
+    let section = |i: usize| sections.get(i).copied().unwrap_or("");
+    let ranges = parsing::lines(section(0), FreshRange::build)
+        .map_err(|e| e.within(contents, section(0)))?;
+    let stock = parsing::lines(section(1), |l| {
+        l.parse::<u64>().map_err(|_| "Invalid stock id")
+    })
+    .map_err(|e| e.within(contents, section(1)))?;
+    Ok(Inventory { ranges, stock })
+}
+
+/// Counts the stock ids that fall in any fresh range.
+pub fn fresh_stock(inventory: &Inventory) -> usize {
+    let index = FreshIndex::build(&inventory.ranges);
+    inventory
+        .stock
+        .iter()
+        .filter(|item| index.contains(**item))
+        .count()
+}
+
+/// Same count as `fresh_stock`, checking each item against every range.
+pub fn fresh_stock_per_item(inventory: &Inventory) -> usize {
+    inventory
+        .stock
+        .iter()
+        .filter(|item| inventory.ranges.iter().any(|r| r.includes(**item)))
+        .count()
+}
+
+/// Same count as `fresh_stock`, scanning the merged ranges in turn instead
+/// of searching them.
+pub fn fresh_stock_merged(inventory: &Inventory) -> usize {
+    let merged = consolidate_ranges(inventory.ranges.clone());
+    inventory
+        .stock
+        .iter()
+        .filter(|item| merged.iter().any(|r| r.includes(**item)))
+        .count()
+}
+
+// Checking ids one at a time is only meant for small inputs.
+const MAX_IDS_PER_ID: u64 = 10_000_000;
+
+/// Same count as `fresh_ids`, checking every id between the lowest and
+/// highest bound against every range.
+pub fn fresh_ids_per_id(inventory: &Inventory) -> Result<u64, &'static str> {
+    let low = inventory.ranges.iter().map(|r| r.low).min().unwrap_or(0);
+    let high = inventory.ranges.iter().map(|r| r.high).max().unwrap_or(0);
+    if high - low >= MAX_IDS_PER_ID {
+        return Err("Too many ids to check one at a time");
+    }
+    Ok((low..=high)
+        .filter(|id| inventory.ranges.iter().any(|r| r.includes(*id)))
+        .count() as u64)
+}
+
+/// Counts every id covered by at least one fresh range.
+pub fn fresh_ids(inventory: &Inventory) -> u64 {
+    let mut fresh_count = 0;
+    for r in consolidate_ranges(inventory.ranges.clone()) {
+        fresh_count += r.len();
+    }
+    fresh_count
+}
+
+/// The fresh ranges merged and sorted, so an id is looked up with a binary
+/// search instead of a check against every range.
+#[derive(Debug, Clone)]
+pub struct FreshIndex {
+    merged: Vec<FreshRange>,
+}
+
+impl FreshIndex {
+    pub fn build(ranges: &[FreshRange]) -> FreshIndex {
+        FreshIndex {
+            merged: consolidate_ranges(ranges.to_vec()),
+        }
+    }
+
+    pub fn contains(&self, id: u64) -> bool {
+        // Merged ranges don't overlap, so they are sorted by their ends too.
+        let i = self.merged.partition_point(|r| r.high < id);
+        self.merged.get(i).is_some_and(|r| r.includes(id))
+    }
+}
+
+/// Whether one stock id is fresh, and which ranges make it so.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ItemReport {
+    pub id: u64,
+    /// Indexes into `Inventory::ranges` of every range holding the id, in
+    /// input order.
+    pub covering: Vec<usize>,
+}
+
+impl ItemReport {
+    pub fn is_fresh(&self) -> bool {
+        !self.covering.is_empty()
+    }
+}
+
+/// One report per stock id, in stock order.
+pub fn stock_report(inventory: &Inventory) -> Vec<ItemReport> {
+    let ranges = &inventory.ranges;
+    // With the ranges ordered by start, those starting at or before an id
+    // are a prefix and only their ends need checking.
+    let mut by_low: Vec<usize> = (0..ranges.len()).collect();
+    by_low.sort_by_key(|i| ranges[*i].low);
+    inventory
+        .stock
+        .iter()
+        .map(|id| {
+            let starts_before = by_low.partition_point(|i| ranges[*i].low <= *id);
+            let mut covering: Vec<usize> = by_low[..starts_before]
+                .iter()
+                .copied()
+                .filter(|i| ranges[*i].high >= *id)
+                .collect();
+            covering.sort_unstable();
+            ItemReport { id: *id, covering }
+        })
+        .collect()
+}
+
+pub fn consolidate_ranges(mut ranges: Vec<FreshRange>) -> Vec<FreshRange> {
+    if ranges.is_empty() {
+        return ranges;
+    }
+    // dbg!(&ranges);
+    ranges.sort_by(|a, b| a.low.partial_cmp(&b.low).unwrap());
+    // dbg!(&ranges);
+    let mut preconsolidated_ranges: Vec<FreshRange> = vec![];
+    preconsolidated_ranges.push(ranges[0].clone());
+    for r in &ranges[1..] {
+        let mut changed = false;
+        for c in &mut preconsolidated_ranges {
+            changed = c.consolidate(r);
+            if changed {
+                break;
+            }
+        }
+        if !changed {
+            preconsolidated_ranges.push(r.clone());
+        }
+    }
+    preconsolidated_ranges
+}
+
+#[derive(Debug, Clone)]
+pub struct FreshRange {
+    pub low: u64,
+    pub high: u64,
+}
+
+impl FreshRange {
+    pub fn build(raw: &str) -> Result<FreshRange, &'static str> {
+        let (first, second) = raw.split_once('-').ok_or("Range is missing a '-'")?;
+        let first = first.parse::<u64>().map_err(|_| "Invalid range start")?;
+        let second = second.parse::<u64>().map_err(|_| "Invalid range end")?;
+        if second < first {
+            return Ok(FreshRange {
+                low: second,
+                high: first,
+            });
+        }
+        Ok(FreshRange {
+            low: first,
+            high: second,
+        })
+    }
+
+    pub fn includes(&self, id: u64) -> bool {
+        id >= self.low && id <= self.high
+    }
+
+    pub fn len(&self) -> u64 {
+        self.high - self.low + 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.high < self.low
+    }
+
+    pub fn consolidate(&mut self, new_range: &FreshRange) -> bool {
+        if self.includes(new_range.low) {
+            if self.includes(new_range.high) {
+                return true;
+            }
+            self.high = new_range.high;
+            return true;
+        }
+        if new_range.includes(self.low) {
+            if new_range.includes(self.high) {
+                self.high = new_range.high;
+            }
+            self.low = new_range.low;
+            return true;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::Rng;
+
+    fn range(low: u64, high: u64) -> FreshRange {
+        FreshRange { low, high }
+    }
+
+    #[test]
+    fn build_orders_bounds() {
+        let r = FreshRange::build("20-10").unwrap();
+        assert_eq!((r.low, r.high), (10, 20));
+    }
+
+    #[test]
+    fn consolidate_overlapping_ranges() {
+        let mut r = range(3, 5);
+        assert!(r.consolidate(&range(4, 8)));
+        assert_eq!((r.low, r.high), (3, 8));
+        assert!(r.consolidate(&range(1, 10)));
+        assert_eq!((r.low, r.high), (1, 10));
+        assert!(r.consolidate(&range(2, 4)));
+        assert_eq!((r.low, r.high), (1, 10));
+        assert!(!r.consolidate(&range(12, 14)));
+    }
+
+    #[test]
+    fn solve_example() {
+        let inventory = parse("3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32\n").unwrap();
+        assert_eq!(fresh_stock(&inventory), 3);
+        assert_eq!(fresh_ids(&inventory), 14);
+        assert_eq!(fresh_stock_merged(&inventory), 3);
+        assert_eq!(fresh_stock_per_item(&inventory), 3);
+        assert_eq!(fresh_ids_per_id(&inventory), Ok(14));
+    }
+
+    #[test]
+    fn stock_report_example() {
+        let inventory = parse("3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32\n").unwrap();
+        let covering: Vec<(u64, Vec<usize>)> = stock_report(&inventory)
+            .into_iter()
+            .map(|r| (r.id, r.covering))
+            .collect();
+        assert_eq!(
+            covering,
+            vec![
+                (1, vec![]),
+                (5, vec![0]),
+                (8, vec![]),
+                (11, vec![1]),
+                (17, vec![2, 3]),
+                (32, vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn fresh_index_matches_brute_force() {
+        let mut rng = Rng::new(47);
+        for _ in 0..300 {
+            let ranges: Vec<FreshRange> = (0..rng.range(0, 10))
+                .map(|_| {
+                    let low = rng.range(0, 100);
+                    range(low, low + rng.range(0, 20))
+                })
+                .collect();
+            let index = FreshIndex::build(&ranges);
+            let inventory = Inventory {
+                ranges: ranges.clone(),
+                stock: (0..=125).collect(),
+            };
+            for report in stock_report(&inventory) {
+                let expected: Vec<usize> = (0..ranges.len())
+                    .filter(|i| ranges[*i].includes(report.id))
+                    .collect();
+                assert_eq!(report.covering, expected);
+                assert_eq!(index.contains(report.id), report.is_fresh(), "{ranges:?}");
+            }
+            assert_eq!(fresh_stock(&inventory), fresh_stock_per_item(&inventory));
+        }
+    }
+
+    #[test]
+    fn consolidate_ranges_example() {
+        let ranges = vec![range(3, 5), range(10, 14), range(16, 20), range(12, 18)];
+        let total: u64 = consolidate_ranges(ranges).iter().map(|r| r.len()).sum();
+        assert_eq!(total, 14);
+    }
+
+    #[test]
+    fn consolidate_ranges_matches_brute_force() {
+        let mut rng = Rng::new(5);
+        for _ in 0..300 {
+            let ranges: Vec<FreshRange> = (0..rng.range(1, 10))
+                .map(|_| {
+                    let low = rng.range(0, 100);
+                    range(low, low + rng.range(0, 20))
+                })
+                .collect();
+            let expected = (0..=120)
+                .filter(|id| ranges.iter().any(|r| r.includes(*id)))
+                .count() as u64;
+            let merged = consolidate_ranges(ranges.clone());
+            let total: u64 = merged.iter().map(|r| r.len()).sum();
+            assert_eq!(total, expected, "{ranges:?}");
+        }
+    }
+}
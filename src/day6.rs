@@ -0,0 +1,567 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+use crate::parse::{self as parsing, Location, ParseError};
+use crate::solution::Solution;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Worksheet;
+
+    fn parse(raw: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(Worksheet::build(raw)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(total(input, ReadingOrder::Rows)?.to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(total(input, ReadingOrder::Columns)?.to_string())
+    }
+}
+
+pub fn parse(raw: &str) -> Result<Worksheet, ParseError> {
+    Worksheet::build(raw)
+}
+
+// `big_total` has none of these limits.
+const OVERFLOW_TOTAL: &str = "Overflow summing totals.";
+const OVERFLOW_EQUATION: &str = "Overflow evaluating equation.";
+const UNDERFLOW_EQUATION: &str = "Subtraction went below zero.";
+
+/// Grand total of every problem, failing if it does not fit in `u64`.
+pub fn total(worksheet: &Worksheet, order: ReadingOrder) -> Result<u64, &'static str> {
+    let mut total: u64 = 0;
+    for e in worksheet.equations(order) {
+        total = total.checked_add(e.result()?).ok_or(OVERFLOW_TOTAL)?;
+    }
+    Ok(total)
+}
+
+/// Grand total of every problem without any risk of overflow.
+pub fn big_total(worksheet: &Worksheet, order: ReadingOrder) -> BigInt {
+    let mut total = BigInt::from(0);
+    for e in worksheet.equations(order) {
+        total = &total + &e.big_result();
+    }
+    total
+}
+
+/// How the digits of a problem are read off the worksheet.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ReadingOrder {
+    /// Each row of a problem is one number (part 1).
+    Rows,
+    /// Each column of a problem is one number, read top to bottom, with the
+    /// problem's columns taken right to left (part 2).
+    Columns,
+}
+
+/// The problems on a worksheet, read both ways. On the page problems are
+/// separated by columns that are entirely blank and the last row holds the
+/// operators.
+#[derive(Debug)]
+pub struct Worksheet {
+    rows: Vec<Equation>,
+    columns: Vec<Equation>,
+}
+
+impl Worksheet {
+    pub fn build(raw: &str) -> Result<Worksheet, ParseError> {
+        let lines: Vec<&str> = raw.lines().filter(|l| !l.trim().is_empty()).collect();
+        let Some((operator_line, number_lines)) = lines.split_last().filter(|(_, n)| !n.is_empty())
+        else {
+            return Err(ParseError::new(
+                "Worksheet needs at least one number row and an operator row.",
+            ));
+        };
+        // Points at char `column` of `line`, which may be padding past its end.
+        let at = |line: &str, column: usize, message: &'static str| {
+            let start = parsing::locate(raw, line);
+            ParseError {
+                location: Some(Location {
+                    line: start.line,
+                    column: start.column + column,
+                }),
+                message: message.to_string(),
+            }
+        };
+        for line in number_lines {
+            if let Some(column) = line.chars().position(|c| !c.is_ascii_digit() && c != ' ') {
+                return Err(at(line, column, "Expected a digit or a space."));
+            }
+        }
+
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        // Pad ragged lines so every column can be read across all rows.
+        let grid: Vec<Vec<char>> = lines
+            .iter()
+            .map(|l| {
+                let mut row: Vec<char> = l.chars().collect();
+                row.resize(width, ' ');
+                row
+            })
+            .collect();
+        let (operators, numbers) = grid.split_last().unwrap();
+
+        let mut worksheet = Worksheet {
+            rows: vec![],
+            columns: vec![],
+        };
+        for (start, end) in parsing::column_spans(&lines) {
+            let column = (start..end)
+                .find(|c| operators[*c] != ' ')
+                .ok_or_else(|| at(operator_line, start, "Problem is missing an operator."))?;
+            let operator =
+                Operator::build(operators[column]).map_err(|e| at(operator_line, column, e))?;
+            for order in [ReadingOrder::Rows, ReadingOrder::Columns] {
+                let mut terms = vec![];
+                for (text, row, column) in read_numbers(numbers, start, end, order) {
+                    let term = text.parse::<u64>().map_err(|_| {
+                        at(number_lines[row], column, "Number does not fit in 64 bits.")
+                    })?;
+                    terms.push(term);
+                }
+                if terms.is_empty() {
+                    return Err(at(operator_line, column, "Problem has no numbers."));
+                }
+                let equation = Equation { terms, operator };
+                match order {
+                    ReadingOrder::Rows => worksheet.rows.push(equation),
+                    ReadingOrder::Columns => worksheet.columns.push(equation),
+                }
+            }
+        }
+        Ok(worksheet)
+    }
+
+    pub fn equations(&self, order: ReadingOrder) -> &[Equation] {
+        match order {
+            ReadingOrder::Rows => &self.rows,
+            ReadingOrder::Columns => &self.columns,
+        }
+    }
+}
+
+// The numbers of the problem in columns `start..end`, each with the row and
+// column of its first digit.
+fn read_numbers(
+    numbers: &[Vec<char>],
+    start: usize,
+    end: usize,
+    order: ReadingOrder,
+) -> Vec<(String, usize, usize)> {
+    match order {
+        ReadingOrder::Rows => numbers
+            .iter()
+            .enumerate()
+            .filter_map(|(row, r)| {
+                let column = (start..end).find(|c| r[*c] != ' ')?;
+                let text = r[column..end].iter().collect::<String>();
+                Some((text.trim_end().to_string(), row, column))
+            })
+            .collect(),
+        // Shorter numbers leave gaps in a column, the digits still read top
+        // to bottom.
+        ReadingOrder::Columns => (start..end)
+            .rev()
+            .filter_map(|column| {
+                let row = numbers.iter().position(|r| r[column] != ' ')?;
+                let text = numbers.iter().map(|r| r[column]).filter(|d| *d != ' ');
+                Some((text.collect(), row, column))
+            })
+            .collect(),
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Operator {
+    Add,
+    Multiply,
+    Subtract,
+    Min,
+    Max,
+    Concatenate,
+}
+
+impl Operator {
+    pub fn build(c: char) -> Result<Operator, &'static str> {
+        match c {
+            '+' => Ok(Operator::Add),
+            '*' => Ok(Operator::Multiply),
+            '-' => Ok(Operator::Subtract),
+            '<' => Ok(Operator::Min),
+            '>' => Ok(Operator::Max),
+            '|' => Ok(Operator::Concatenate),
+            _ => Err("Invalid operator, expected one of + * - < > |"),
+        }
+    }
+
+    fn apply(&self, a: u64, b: u64) -> Result<u64, &'static str> {
+        match self {
+            Operator::Add => a.checked_add(b).ok_or(OVERFLOW_EQUATION),
+            Operator::Multiply => a.checked_mul(b).ok_or(OVERFLOW_EQUATION),
+            Operator::Subtract => a.checked_sub(b).ok_or(UNDERFLOW_EQUATION),
+            Operator::Min => Ok(a.min(b)),
+            Operator::Max => Ok(a.max(b)),
+            Operator::Concatenate => 10u64
+                .checked_pow(b.checked_ilog10().unwrap_or(0) + 1)
+                .and_then(|shift| a.checked_mul(shift))
+                .and_then(|v| v.checked_add(b))
+                .ok_or(OVERFLOW_EQUATION),
+        }
+    }
+
+    fn apply_big(&self, a: &BigInt, b: u64) -> BigInt {
+        let big_b = BigInt::from(b);
+        match self {
+            Operator::Add => a + &big_b,
+            Operator::Multiply => a * &big_b,
+            Operator::Subtract => a - &big_b,
+            Operator::Min => a.clone().min(big_b),
+            Operator::Max => a.clone().max(big_b),
+            Operator::Concatenate => {
+                let shift = BigInt::pow10(b.checked_ilog10().unwrap_or(0) + 1);
+                let shifted = a * &shift;
+                // Keep the sign of the left operand: -12 | 3 is -123.
+                if a.negative {
+                    &shifted - &big_b
+                } else {
+                    &shifted + &big_b
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Equation {
+    pub terms: Vec<u64>,
+    pub operator: Operator,
+}
+
+impl Equation {
+    /// Folds the terms left to right, failing if any step leaves `u64`.
+    pub fn result(&self) -> Result<u64, &'static str> {
+        let mut terms = self.terms.iter();
+        let mut result = *terms.next().ok_or("Equation has no terms.")?;
+        for t in terms {
+            result = self.operator.apply(result, *t)?;
+        }
+        Ok(result)
+    }
+
+    /// Folds the terms left to right without any risk of overflow.
+    pub fn big_result(&self) -> BigInt {
+        let mut terms = self.terms.iter();
+        let mut result = BigInt::from(terms.next().copied().unwrap_or(0));
+        for t in terms {
+            result = self.operator.apply_big(&result, *t);
+        }
+        result
+    }
+}
+
+// Limbs are stored little endian in base 10^9 so `Display` is straightforward.
+const LIMB_BASE: u64 = 1_000_000_000;
+
+/// Minimal signed arbitrary precision integer, just enough for `Operator`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigInt {
+    negative: bool,
+    limbs: Vec<u32>,
+}
+
+impl From<u64> for BigInt {
+    fn from(value: u64) -> BigInt {
+        let mut limbs = vec![];
+        let mut v = value;
+        while v > 0 {
+            limbs.push((v % LIMB_BASE) as u32);
+            v /= LIMB_BASE;
+        }
+        BigInt {
+            negative: false,
+            limbs,
+        }
+    }
+}
+
+impl BigInt {
+    fn pow10(exp: u32) -> BigInt {
+        let mut result = BigInt::from(1);
+        for _ in 0..exp {
+            result = &result * &BigInt::from(10);
+        }
+        result
+    }
+
+    fn normalized(mut self) -> BigInt {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        if self.limbs.is_empty() {
+            self.negative = false;
+        }
+        self
+    }
+
+    fn cmp_magnitude(a: &[u32], b: &[u32]) -> std::cmp::Ordering {
+        a.len()
+            .cmp(&b.len())
+            .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+    }
+
+    fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let mut result = vec![];
+        let mut carry = 0;
+        for i in 0..a.len().max(b.len()) {
+            let sum = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+            result.push((sum % LIMB_BASE) as u32);
+            carry = sum / LIMB_BASE;
+        }
+        if carry > 0 {
+            result.push(carry as u32);
+        }
+        result
+    }
+
+    // Requires |a| >= |b|.
+    fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let mut result = vec![];
+        let mut borrow = 0;
+        for (i, limb) in a.iter().enumerate() {
+            let mut diff = *limb as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+            borrow = 0;
+            if diff < 0 {
+                diff += LIMB_BASE as i64;
+                borrow = 1;
+            }
+            result.push(diff as u32);
+        }
+        result
+    }
+}
+
+impl Add<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt {
+                negative: self.negative,
+                limbs: BigInt::add_magnitude(&self.limbs, &other.limbs),
+            }
+            .normalized();
+        }
+        match BigInt::cmp_magnitude(&self.limbs, &other.limbs) {
+            std::cmp::Ordering::Less => BigInt {
+                negative: other.negative,
+                limbs: BigInt::sub_magnitude(&other.limbs, &self.limbs),
+            },
+            _ => BigInt {
+                negative: self.negative,
+                limbs: BigInt::sub_magnitude(&self.limbs, &other.limbs),
+            },
+        }
+        .normalized()
+    }
+}
+
+impl Sub<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        let negated = BigInt {
+            negative: !other.negative,
+            limbs: other.limbs.clone(),
+        };
+        self + &negated.normalized()
+    }
+}
+
+impl Mul<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        let mut result = vec![0u64; self.limbs.len() + other.limbs.len() + 1];
+        for (i, a) in self.limbs.iter().enumerate() {
+            let mut carry = 0;
+            for (j, b) in other.limbs.iter().enumerate() {
+                let cur = result[i + j] + *a as u64 * *b as u64 + carry;
+                result[i + j] = cur % LIMB_BASE;
+                carry = cur / LIMB_BASE;
+            }
+            let mut k = i + other.limbs.len();
+            while carry > 0 {
+                let cur = result[k] + carry;
+                result[k] = cur % LIMB_BASE;
+                carry = cur / LIMB_BASE;
+                k += 1;
+            }
+        }
+        BigInt {
+            negative: self.negative != other.negative,
+            limbs: result.into_iter().map(|l| l as u32).collect(),
+        }
+        .normalized()
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &BigInt) -> std::cmp::Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => std::cmp::Ordering::Greater,
+            (true, false) => std::cmp::Ordering::Less,
+            (false, false) => BigInt::cmp_magnitude(&self.limbs, &other.limbs),
+            (true, true) => BigInt::cmp_magnitude(&other.limbs, &self.limbs),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &BigInt) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((most, rest)) = self.limbs.split_last() else {
+            return write!(f, "0");
+        };
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{most}")?;
+        for limb in rest.iter().rev() {
+            write!(f, "{limb:09}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  \n";
+
+    fn equation(operator: Operator, terms: &[u64]) -> Equation {
+        Equation {
+            terms: terms.to_vec(),
+            operator,
+        }
+    }
+
+    #[test]
+    fn result_applies_each_operator() {
+        assert_eq!(equation(Operator::Add, &[1, 2, 3]).result(), Ok(6));
+        assert_eq!(equation(Operator::Multiply, &[2, 3, 4]).result(), Ok(24));
+        assert_eq!(equation(Operator::Subtract, &[10, 3, 2]).result(), Ok(5));
+        assert_eq!(equation(Operator::Min, &[7, 2, 9]).result(), Ok(2));
+        assert_eq!(equation(Operator::Max, &[7, 2, 9]).result(), Ok(9));
+        assert_eq!(
+            equation(Operator::Concatenate, &[12, 0, 345]).result(),
+            Ok(120345)
+        );
+    }
+
+    #[test]
+    fn result_reports_overflow() {
+        assert!(
+            equation(Operator::Multiply, &[u64::MAX, 2])
+                .result()
+                .is_err()
+        );
+        assert_eq!(
+            equation(Operator::Subtract, &[1, 2]).result(),
+            Err(UNDERFLOW_EQUATION)
+        );
+        assert_eq!(
+            equation(Operator::Add, &[u64::MAX, 1]).result(),
+            Err(OVERFLOW_EQUATION)
+        );
+        assert!(
+            equation(Operator::Concatenate, &[u64::MAX, 1])
+                .result()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn big_result_matches_checked_result() {
+        let e = equation(Operator::Multiply, &[u64::MAX, u64::MAX]);
+        assert_eq!(
+            e.big_result().to_string(),
+            "340282366920938463426481119284349108225"
+        );
+        let e = equation(Operator::Subtract, &[1, 1_000_000_000_000]);
+        assert_eq!(e.big_result().to_string(), "-999999999999");
+        let e = equation(Operator::Concatenate, &[7, 0, 42]);
+        assert_eq!(e.big_result().to_string(), "7042");
+    }
+
+    #[test]
+    fn operator_rejects_unknown_characters() {
+        assert_eq!(Operator::build('*'), Ok(Operator::Multiply));
+        assert!(Operator::build('/').is_err());
+    }
+
+    #[test]
+    fn worksheet_reads_rows_and_columns() {
+        let worksheet = Worksheet::build(EXAMPLE).unwrap();
+        let total = |order| -> u64 {
+            worksheet
+                .equations(order)
+                .iter()
+                .map(|e| e.result().unwrap())
+                .sum()
+        };
+        assert_eq!(total(ReadingOrder::Rows), 4277556);
+        assert_eq!(total(ReadingOrder::Columns), 3263827);
+    }
+
+    #[test]
+    fn worksheet_errors_are_located() {
+        let error = |raw| Worksheet::build(raw).unwrap_err().to_string();
+        assert_eq!(
+            error("12 3\n4x 5\n+  *\n"),
+            "line 2, column 2: Expected a digit or a space."
+        );
+        assert_eq!(
+            error("12 3\n45 6\n+  /\n"),
+            "line 3, column 4: Invalid operator, expected one of + * - < > |"
+        );
+        assert_eq!(
+            error("12 3\n45 6\n+\n"),
+            "line 3, column 4: Problem is missing an operator."
+        );
+        assert_eq!(
+            error("1 99999999999999999999\n2 3\n+ *\n"),
+            "line 1, column 3: Number does not fit in 64 bits."
+        );
+        assert_eq!(
+            Worksheet::build("+\n").unwrap_err().to_string(),
+            "Worksheet needs at least one number row and an operator row."
+        );
+    }
+
+    #[test]
+    fn worksheet_columns_skip_gaps() {
+        let worksheet = Worksheet::build("12\n3 \n45\n+ ").unwrap();
+        let equations = worksheet.equations(ReadingOrder::Columns);
+        assert_eq!(equations[0].terms, vec![25, 134]);
+    }
+
+    #[test]
+    fn worksheet_handles_ragged_rows() {
+        let worksheet = Worksheet::build("1 20\n2 3\n4\n5\n+ *").unwrap();
+        let equations = worksheet.equations(ReadingOrder::Rows);
+        assert_eq!(equations[0].terms, vec![1, 2, 4, 5]);
+        assert_eq!(equations[1].terms, vec![20, 3]);
+    }
+}
@@ -0,0 +1,347 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::parse::{self as parsing, ParseError};
+use crate::solution::Solution;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = TachyonManifold;
+
+    fn parse(raw: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse(raw)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(input.clone().simulate()?.splits.to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(input.clone().simulate()?.timelines.to_string())
+    }
+}
+
+pub fn parse(raw: &str) -> Result<TachyonManifold, ParseError> {
+    TachyonManifold::build(raw)
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CellContents {
+    Empty,
+    Start,
+    Splitter,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct BeamReport {
+    pub splits: u64,
+    pub timelines: u128,
+}
+
+#[derive(Clone)]
+pub struct TachyonManifold {
+    grid: Vec<Vec<CellContents>>,
+    // Number of timelines passing through each cell, filled in by `simulate`.
+    beams: Vec<Vec<u128>>,
+    width: usize,
+}
+
+impl TachyonManifold {
+    pub fn build(raw: &str) -> Result<TachyonManifold, ParseError> {
+        let r_grid = parsing::grid(raw, |c| match c {
+            // Beams drawn into the diagram are recomputed by `simulate`.
+            '.' | '|' => Ok(CellContents::Empty),
+            'S' => Ok(CellContents::Start),
+            '^' => Ok(CellContents::Splitter),
+            _ => Err("unknown character in manifold"),
+        })?;
+        let width = r_grid[0].len();
+        Ok(TachyonManifold {
+            beams: vec![vec![0; width]; r_grid.len()],
+            width,
+            grid: r_grid,
+        })
+    }
+
+    /// Sends the beam down the manifold one row at a time, counting how many
+    /// splitters are hit (part 1) and how many timelines leave the bottom row
+    /// (part 2). Each cell holds the number of timelines passing through it,
+    /// so a splitter hit by `n` timelines hands `n` to each of its neighbours.
+    pub fn simulate(&mut self) -> Result<BeamReport, &'static str> {
+        let mut splits = 0;
+        let mut current = vec![0u128; self.width];
+        for (row, layer) in self.grid.iter().enumerate() {
+            let mut next = vec![0u128; self.width];
+            for (i, cell) in layer.iter().enumerate() {
+                let n = current[i];
+                match cell {
+                    CellContents::Start => add_beam(&mut next, i, n.max(1))?,
+                    CellContents::Empty => add_beam(&mut next, i, n)?,
+                    CellContents::Splitter => {
+                        if n == 0 {
+                            continue;
+                        }
+                        splits += 1;
+                        if i > 0 {
+                            add_beam(&mut next, i - 1, n)?;
+                        }
+                        if i + 1 < self.width {
+                            add_beam(&mut next, i + 1, n)?;
+                        }
+                    }
+                }
+            }
+            self.beams[row] = next.clone();
+            current = next;
+        }
+
+        let mut timelines: u128 = 0;
+        for n in &current {
+            timelines = timelines
+                .checked_add(*n)
+                .ok_or("Timeline count overflowed")?;
+        }
+        Ok(BeamReport { splits, timelines })
+    }
+}
+
+// Characters used for increasing beam intensity in `heatmap`, lowest first.
+const HEAT_RAMP: [char; 8] = [':', '-', '=', '+', '*', '#', '%', '@'];
+
+impl TachyonManifold {
+    /// Renders the beam counts as one character per cell on a log2 scale so
+    /// the columns stay aligned however large the counts grow. A legend of the
+    /// count range covered by each character follows the grid.
+    pub fn heatmap(&self) -> String {
+        let max = self.beams.iter().flatten().copied().max().unwrap_or(0);
+        let bits = if max == 0 { 1 } else { max.ilog2() + 1 };
+        let span = bits.div_ceil(HEAT_RAMP.len() as u32);
+
+        let mut out = String::new();
+        for (line, beams) in self.grid.iter().zip(&self.beams) {
+            for (v, n) in line.iter().zip(beams) {
+                out.push(match v {
+                    CellContents::Start => 'S',
+                    CellContents::Splitter => '^',
+                    CellContents::Empty if *n == 0 => '.',
+                    CellContents::Empty => HEAT_RAMP[(n.ilog2() / span) as usize],
+                });
+            }
+            out.push('\n');
+        }
+
+        out.push_str("Legend:\n");
+        for (level, c) in HEAT_RAMP.iter().enumerate() {
+            let low = 1u128 << (level as u32 * span).min(127);
+            if low > max {
+                break;
+            }
+            let high = (1u128 << ((level as u32 + 1) * span).min(127)) - 1;
+            out.push_str(&format!("  {c}  {low}..={}\n", high.min(max)));
+        }
+        out
+    }
+
+    /// Writes the splitters that are hit as a Graphviz digraph. Every edge is a
+    /// beam leaving a start or splitter, labelled with the number of timelines
+    /// it carries, ending at the next splitter below it or at an exit node.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph manifold {\n");
+        for (row, line) in self.grid.iter().enumerate() {
+            for (col, cell) in line.iter().enumerate() {
+                let (outgoing, label) = match cell {
+                    CellContents::Start => (vec![col], "S"),
+                    CellContents::Splitter if self.incoming(row, col) > 0 => {
+                        let mut cols = vec![];
+                        if col > 0 {
+                            cols.push(col - 1);
+                        }
+                        if col + 1 < self.width {
+                            cols.push(col + 1);
+                        }
+                        (cols, "^")
+                    }
+                    _ => continue,
+                };
+                let n = self.incoming(row, col).max(1);
+                out.push_str(&format!(
+                    "  r{row}c{col} [label=\"{label} ({row},{col})\\n{n}\"];\n"
+                ));
+                for c in outgoing {
+                    let target = match (row + 1..self.grid.len())
+                        .find(|r| self.grid[*r][c] == CellContents::Splitter)
+                    {
+                        Some(r) => format!("r{r}c{c}"),
+                        None => format!("exit{c}"),
+                    };
+                    out.push_str(&format!("  r{row}c{col} -> {target} [label=\"{n}\"];\n"));
+                }
+            }
+        }
+        if let Some(last) = self.beams.last() {
+            for (c, n) in last.iter().enumerate().filter(|(_, n)| **n > 0) {
+                out.push_str(&format!(
+                    "  exit{c} [shape=box, label=\"exit {c}\\n{n}\"];\n"
+                ));
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Timelines arriving at a cell from the row above.
+    fn incoming(&self, row: usize, col: usize) -> u128 {
+        if row == 0 {
+            0
+        } else {
+            self.beams[row - 1][col]
+        }
+    }
+}
+
+fn add_beam(row: &mut [u128], i: usize, n: u128) -> Result<(), &'static str> {
+    row[i] = row[i].checked_add(n).ok_or("Timeline count overflowed")?;
+    Ok(())
+}
+
+impl fmt::Debug for TachyonManifold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Manifold: [")?;
+        for (line, beams) in self.grid.iter().zip(&self.beams) {
+            let mut c_line = String::new();
+            for (v, n) in line.iter().zip(beams) {
+                match v {
+                    CellContents::Empty if *n > 0 => c_line.push_str(&n.to_string()),
+                    CellContents::Empty => c_line.push('.'),
+                    CellContents::Start => c_line.push('S'),
+                    CellContents::Splitter => c_line.push('^'),
+                }
+            }
+            writeln!(f, "{}", c_line)?;
+        }
+        write!(f, "]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::Rng;
+    use std::collections::HashSet;
+    use std::fs;
+
+    // Follows every timeline individually, recording the splitters it hits.
+    fn brute_timelines(
+        grid: &[Vec<char>],
+        row: usize,
+        col: usize,
+        hit: &mut HashSet<(usize, usize)>,
+    ) -> u128 {
+        if row == grid.len() {
+            return 1;
+        }
+        if grid[row][col] != '^' {
+            return brute_timelines(grid, row + 1, col, hit);
+        }
+        hit.insert((row, col));
+        let mut total = 0;
+        if col > 0 {
+            total += brute_timelines(grid, row + 1, col - 1, hit);
+        }
+        if col + 1 < grid[row].len() {
+            total += brute_timelines(grid, row + 1, col + 1, hit);
+        }
+        total
+    }
+
+    #[test]
+    fn simulate_example() {
+        let raw = fs::read_to_string("challenges/day7/test.txt").unwrap();
+        let mut manifold = TachyonManifold::build(&raw).unwrap();
+        let report = manifold.simulate().unwrap();
+        assert_eq!(report.splits, 21);
+        assert_eq!(report.timelines, 40);
+    }
+
+    #[test]
+    fn simulate_splitter_on_edge() {
+        let mut manifold = TachyonManifold::build("S.\n^.\n..\n").unwrap();
+        let report = manifold.simulate().unwrap();
+        assert_eq!(report.splits, 1);
+        assert_eq!(report.timelines, 1);
+    }
+
+    #[test]
+    fn simulate_matches_brute_force() {
+        let mut rng = Rng::new(7);
+        for _ in 0..200 {
+            let width = rng.range(1, 9) as usize;
+            let height = rng.range(1, 10) as usize;
+            let start = rng.range(0, width as u64 - 1) as usize;
+            let mut grid: Vec<Vec<char>> = vec![vec!['.'; width]];
+            grid[0][start] = 'S';
+            for _ in 0..height {
+                grid.push(
+                    (0..width)
+                        .map(|_| if rng.chance(1, 3) { '^' } else { '.' })
+                        .collect(),
+                );
+            }
+            let raw: String = grid
+                .iter()
+                .map(|row| row.iter().collect::<String>() + "\n")
+                .collect();
+            let mut hit = HashSet::new();
+            let timelines = brute_timelines(&grid, 1, start, &mut hit);
+
+            let mut manifold = TachyonManifold::build(&raw).unwrap();
+            let report = manifold.simulate().unwrap();
+            assert_eq!(report.timelines, timelines, "{raw}");
+            assert_eq!(report.splits, hit.len() as u64, "{raw}");
+        }
+    }
+
+    #[test]
+    fn dot_follows_each_beam() {
+        let mut manifold = TachyonManifold::build(".S.\n...\n.^.\n...\n").unwrap();
+        manifold.simulate().unwrap();
+        assert_eq!(
+            manifold.to_dot(),
+            "digraph manifold {
+  r0c1 [label=\"S (0,1)\\n1\"];
+  r0c1 -> r2c1 [label=\"1\"];
+  r2c1 [label=\"^ (2,1)\\n1\"];
+  r2c1 -> exit0 [label=\"1\"];
+  r2c1 -> exit2 [label=\"1\"];
+  exit0 [shape=box, label=\"exit 0\\n1\"];
+  exit2 [shape=box, label=\"exit 2\\n1\"];
+}
+"
+        );
+
+        let raw = fs::read_to_string("challenges/day7/test.txt").unwrap();
+        let mut manifold = TachyonManifold::build(&raw).unwrap();
+        let report = manifold.simulate().unwrap();
+        let dot = manifold.to_dot();
+        assert_eq!(dot.matches("[label=\"^ (").count() as u64, report.splits);
+        let exits: u128 = dot
+            .lines()
+            .filter(|l| l.contains("shape=box"))
+            .map(|l| l.rsplit("\\n").next().unwrap().trim_end_matches("\"];"))
+            .map(|n| n.parse::<u128>().unwrap())
+            .sum();
+        assert_eq!(exits, report.timelines as u128);
+    }
+
+    #[test]
+    fn heatmap_keeps_columns_aligned() {
+        let raw = fs::read_to_string("challenges/day7/test.txt").unwrap();
+        let mut manifold = TachyonManifold::build(&raw).unwrap();
+        manifold.simulate().unwrap();
+        let heatmap = manifold.heatmap();
+        let rows: Vec<&str> = heatmap.lines().take_while(|l| *l != "Legend:").collect();
+        assert_eq!(rows.len(), manifold.grid.len());
+        assert!(rows.iter().all(|r| r.chars().count() == manifold.width));
+    }
+}
@@ -0,0 +1,560 @@
+use std::error::Error;
+use std::fmt::Write;
+
+use crate::geometry::Point3;
+use crate::parse::{self as parsing, ParseError};
+use crate::solution::Solution;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Playground;
+
+    fn parse(raw: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse(raw)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(largest_circuits(input, 3).to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(wall_length(input)?.to_string())
+    }
+}
+
+#[derive(Debug)]
+pub struct Playground {
+    pub boxes: Vec<JunctionBox>,
+    /// How many of the shortest connections part 1 makes.
+    pub connections: usize,
+}
+
+/// How many connections part 1 makes on a real input. The puzzle's example
+/// makes 10 instead.
+pub const CONNECTIONS: usize = 1000;
+
+pub fn parse(contents: &str) -> Result<Playground, ParseError> {
+    parse_with(contents, CONNECTIONS)
+}
+
+/// Same as `parse`, with part 1 making `connections` connections.
+pub fn parse_with(contents: &str, connections: usize) -> Result<Playground, ParseError> {
+    let boxes = parsing::lines(contents, parse_box)?;
+    if boxes.len() < 2 {
+        return Err(ParseError::new("Need at least two junction boxes"));
+    }
+    Ok(Playground { boxes, connections })
+}
+
+/// Every pair of boxes, shortest first. Ties keep the order
+/// `JunctionPlan::pop_minimum_distance` would pop them in.
+pub fn sorted_connections(boxes: &[JunctionBox]) -> Vec<Connection> {
+    let mut connections = vec![];
+    for (i, junction) in boxes.iter().enumerate() {
+        for (j, other) in boxes[i + 1..].iter().enumerate() {
+            connections.push(Connection {
+                weight: junction.distance_squared(other),
+                box1: i,
+                box2: j + i + 1,
+            });
+        }
+    }
+    connections.sort_by_key(|c| c.weight);
+    connections
+}
+
+/// The circuits built so far. Boxes that were never connected are not listed.
+#[derive(Debug, Default)]
+pub struct Circuits {
+    pub networks: Vec<Network>,
+}
+
+impl Circuits {
+    pub fn connect(&mut self, min: Connection) {
+        let possible_nets: Vec<usize> = self
+            .networks
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| min.is_in(n))
+            .map(|(i, _)| i)
+            .collect();
+        if possible_nets.is_empty() {
+            self.networks.push(Network::build(min));
+        } else {
+            // println!("Merging.");
+            for i in possible_nets[1..].iter().rev() {
+                let merged = self.networks.remove(*i);
+                self.networks[possible_nets[0]].merge(merged);
+            }
+            self.networks[possible_nets[0]].add(min);
+        }
+    }
+
+    /// Size of every circuit, largest first, counting lone boxes as size 1.
+    pub fn sizes(&self, box_count: usize) -> Vec<usize> {
+        let mut sizes: Vec<usize> = self.networks.iter().map(|n| n.node_list.len()).collect();
+        let connected: usize = sizes.iter().sum();
+        sizes.extend(std::iter::repeat_n(1, box_count - connected));
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+}
+
+/// Joins the `connections` closest pairs and multiplies the sizes of the `k`
+/// largest circuits.
+pub fn largest_circuits(playground: &Playground, k: usize) -> u64 {
+    circuit_product(playground, k, sorted_connections(&playground.boxes))
+}
+
+/// Keeps joining the closest pairs until every box is in one circuit, then
+/// multiplies the X coordinates of the last pair joined.
+pub fn wall_length(playground: &Playground) -> Result<i64, &'static str> {
+    last_join(playground, sorted_connections(&playground.boxes))
+}
+
+// Popping from the matrix rescans every pair, so it is only for small inputs.
+const MAX_MATRIX_BOXES: usize = 200;
+
+/// Same as `largest_circuits`, taking pairs from a `JunctionPlan`.
+pub fn largest_circuits_matrix(playground: &Playground, k: usize) -> Result<u64, &'static str> {
+    Ok(circuit_product(
+        playground,
+        k,
+        matrix_connections(playground)?,
+    ))
+}
+
+/// Same as `wall_length`, taking pairs from a `JunctionPlan`.
+pub fn wall_length_matrix(playground: &Playground) -> Result<i64, &'static str> {
+    last_join(playground, matrix_connections(playground)?)
+}
+
+fn matrix_connections(
+    playground: &Playground,
+) -> Result<impl Iterator<Item = Connection>, &'static str> {
+    if playground.boxes.len() > MAX_MATRIX_BOXES {
+        return Err("Too many junction boxes for the distance matrix");
+    }
+    Ok(JunctionPlan::build(&playground.boxes).into_connections())
+}
+
+fn circuit_product(
+    playground: &Playground,
+    k: usize,
+    connections: impl IntoIterator<Item = Connection>,
+) -> u64 {
+    let mut circuits = Circuits::default();
+    for c in connections.into_iter().take(playground.connections) {
+        circuits.connect(c);
+    }
+    circuits
+        .sizes(playground.boxes.len())
+        .iter()
+        .take(k)
+        .map(|s| *s as u64)
+        .product()
+}
+
+fn last_join(
+    playground: &Playground,
+    connections: impl IntoIterator<Item = Connection>,
+) -> Result<i64, &'static str> {
+    let junction_boxes = &playground.boxes;
+    let mut circuits = Circuits::default();
+    for min in connections {
+        let persistent_min = min.clone();
+        circuits.connect(min);
+        let net = &circuits.networks;
+        if net.len() == 1 && net[0].node_list.len() == junction_boxes.len() {
+            return Ok(
+                junction_boxes[persistent_min.box1].x * junction_boxes[persistent_min.box2].x
+            );
+        }
+    }
+    Err("Junction boxes never formed a single circuit")
+}
+
+/// One circuit in a `CircuitReport`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CircuitSummary {
+    pub id: usize,
+    /// Indexes into `Playground::boxes`, ascending.
+    pub members: Vec<usize>,
+    /// Every connection made inside the circuit, in the order it was made.
+    pub edges: Vec<Connection>,
+}
+
+/// The circuits after joining some number of the closest pairs, largest
+/// first. Lone boxes are listed as circuits of one with no edges.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CircuitReport {
+    pub connections: usize,
+    pub circuits: Vec<CircuitSummary>,
+    /// Size of every circuit, largest first.
+    pub sizes: Vec<usize>,
+    pub k: usize,
+    /// Product of the `k` largest sizes, the part 1 answer.
+    pub top_product: u64,
+}
+
+/// Joins the `connections` closest pairs and describes the circuits formed.
+pub fn circuit_report(playground: &Playground, connections: usize, k: usize) -> CircuitReport {
+    let mut circuits = Circuits::default();
+    for c in sorted_connections(&playground.boxes)
+        .into_iter()
+        .take(connections)
+    {
+        circuits.connect(c);
+    }
+    let mut joined = vec![false; playground.boxes.len()];
+    let mut groups: Vec<(Vec<usize>, Vec<Connection>)> = vec![];
+    for network in circuits.networks {
+        let mut members = network.node_list;
+        members.sort_unstable();
+        for m in &members {
+            joined[*m] = true;
+        }
+        groups.push((members, network.nodes));
+    }
+    for (i, _) in joined.iter().enumerate().filter(|(_, j)| !**j) {
+        groups.push((vec![i], vec![]));
+    }
+    groups.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then(a.0[0].cmp(&b.0[0])));
+
+    let sizes: Vec<usize> = groups.iter().map(|g| g.0.len()).collect();
+    let top_product = sizes.iter().take(k).map(|s| *s as u64).product();
+    let circuits = groups
+        .into_iter()
+        .enumerate()
+        .map(|(id, (members, edges))| CircuitSummary { id, members, edges })
+        .collect();
+    CircuitReport {
+        connections,
+        circuits,
+        sizes,
+        k,
+        top_product,
+    }
+}
+
+/// One row per circuit, largest first, so the size column is the sorted
+/// size list. Members are `x,y,z` coordinates and edges `box1-box2` pairs of
+/// box indexes, each separated by spaces. Every row repeats `k` and the
+/// product of the `k` largest sizes so the file stands on its own.
+pub fn report_csv(playground: &Playground, report: &CircuitReport) -> String {
+    let mut out = String::from("circuit,size,members,edges,k,top_product\n");
+    for c in &report.circuits {
+        let members: Vec<String> = c
+            .members
+            .iter()
+            .map(|m| {
+                let b = playground.boxes[*m];
+                format!("{},{},{}", b.x, b.y, b.z)
+            })
+            .collect();
+        let edges: Vec<String> = c
+            .edges
+            .iter()
+            .map(|e| format!("{}-{}", e.box1, e.box2))
+            .collect();
+        writeln!(
+            out,
+            "{},{},\"{}\",\"{}\",{},{}",
+            c.id,
+            c.members.len(),
+            members.join(" "),
+            edges.join(" "),
+            report.k,
+            report.top_product
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// The whole report as a single JSON object.
+pub fn report_json(playground: &Playground, report: &CircuitReport) -> String {
+    let join = |items: Vec<String>| items.join(",");
+    let circuits: Vec<String> = report
+        .circuits
+        .iter()
+        .map(|c| {
+            let members = c.members.iter().map(|m| {
+                let b = playground.boxes[*m];
+                format!("{{\"box\":{m},\"x\":{},\"y\":{},\"z\":{}}}", b.x, b.y, b.z)
+            });
+            let edges = c.edges.iter().map(|e| {
+                format!(
+                    "{{\"box1\":{},\"box2\":{},\"distance_squared\":{}}}",
+                    e.box1, e.box2, e.weight
+                )
+            });
+            format!(
+                "{{\"id\":{},\"size\":{},\"members\":[{}],\"edges\":[{}]}}",
+                c.id,
+                c.members.len(),
+                join(members.collect()),
+                join(edges.collect())
+            )
+        })
+        .collect();
+    format!(
+        "{{\"connections\":{},\"k\":{},\"top_product\":{},\"sizes\":[{}],\"circuits\":[{}]}}\n",
+        report.connections,
+        report.k,
+        report.top_product,
+        join(report.sizes.iter().map(|s| s.to_string()).collect()),
+        join(circuits)
+    )
+}
+
+pub type JunctionBox = Point3<i64>;
+
+pub fn parse_box(raw: &str) -> Result<JunctionBox, &'static str> {
+    let terms = raw
+        .split(',')
+        .map(|n: &str| n.trim().parse::<i64>())
+        .collect::<Result<Vec<i64>, _>>()
+        .map_err(|_| "Invalid junction box coordinate")?;
+    if terms.len() != 3 {
+        return Err("Junction box needs exactly three coordinates");
+    }
+    Ok(Point3::new(terms[0], terms[1], terms[2]))
+}
+
+/// A pair of boxes, weighted by their squared distance so comparisons stay
+/// exact.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Connection {
+    pub weight: i64,
+    pub box1: usize,
+    pub box2: usize,
+}
+
+impl Connection {
+    pub fn is_in(&self, n: &Network) -> bool {
+        n.node_list
+            .iter()
+            .any(|v| *v == self.box1 || *v == self.box2)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Network {
+    pub weight: i64,
+    pub nodes: Vec<Connection>,
+    pub node_list: Vec<usize>,
+}
+
+impl Network {
+    pub fn build(c: Connection) -> Network {
+        Network {
+            weight: c.weight,
+            node_list: vec![c.box1, c.box2],
+            nodes: vec![c],
+        }
+    }
+    pub fn add(&mut self, c: Connection) {
+        if self.node_list.iter().all(|v| *v != c.box1) {
+            self.node_list.push(c.box1);
+        }
+        if self.node_list.iter().all(|v| *v != c.box2) {
+            self.node_list.push(c.box2);
+        }
+        self.weight += c.weight;
+        self.nodes.push(c);
+    }
+
+    pub fn merge(&mut self, n: Network) {
+        for c in n.nodes {
+            self.add(c);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct JunctionPlan {
+    /// Squared distance from box `row` to box `column` for `column > row`,
+    /// `None` below the diagonal and once a pair has been popped.
+    pub weights: Vec<Vec<Option<i64>>>,
+}
+
+impl JunctionPlan {
+    pub fn build(boxes: &[JunctionBox]) -> JunctionPlan {
+        let mut weights: Vec<Vec<Option<i64>>> =
+            vec![vec![None; boxes.len()]; boxes.len().saturating_sub(1)];
+        for (i, junction) in boxes.iter().enumerate() {
+            for (j, other) in boxes[i + 1..].iter().enumerate() {
+                let k = j + i + 1;
+                weights[i][k] = Some(junction.distance_squared(other));
+            }
+        }
+        JunctionPlan { weights }
+    }
+
+    /// The closest pair not popped yet, `None` once every pair has been.
+    /// Ties go to the pair found first reading the matrix row by row.
+    pub fn pop_minimum_distance(&mut self) -> Option<Connection> {
+        let mut best: Option<(i64, usize, usize)> = None;
+        for (i, row) in self.weights.iter().enumerate() {
+            let res = row
+                .iter()
+                .enumerate()
+                .filter_map(|(j, v)| v.map(|v| (j, v)))
+                .min_by_key(|(_, v)| *v);
+            if let Some((j, v)) = res
+                && best.is_none_or(|(min, _, _)| v < min)
+            {
+                best = Some((v, i, j));
+            }
+        }
+        let (weight, box1, box2) = best?;
+        self.weights[box1][box2] = None;
+        Some(Connection { weight, box1, box2 })
+    }
+
+    /// Pops every pair, shortest first.
+    pub fn into_connections(mut self) -> impl Iterator<Item = Connection> {
+        std::iter::from_fn(move || self.pop_minimum_distance())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::Rng;
+    use std::fs;
+
+    fn junction(x: i64, y: i64, z: i64) -> JunctionBox {
+        JunctionBox { x, y, z }
+    }
+
+    #[test]
+    fn build_parses_coordinates() {
+        let b = parse_box("162,817,812").unwrap();
+        assert_eq!((b.x, b.y, b.z), (162, 817, 812));
+    }
+
+    #[test]
+    fn solve_example() {
+        let raw = fs::read_to_string("challenges/day8/test.txt").unwrap();
+        assert_eq!(parse(&raw).unwrap().connections, CONNECTIONS);
+        let playground = parse_with(&raw, 10).unwrap();
+        assert_eq!(largest_circuits(&playground, 3), 40);
+        assert_eq!(wall_length(&playground), Ok(25272));
+        assert_eq!(largest_circuits_matrix(&playground, 3), Ok(40));
+        assert_eq!(wall_length_matrix(&playground), Ok(25272));
+    }
+
+    #[test]
+    fn circuit_report_example() {
+        let raw = fs::read_to_string("challenges/day8/test.txt").unwrap();
+        let playground = parse_with(&raw, 10).unwrap();
+        let report = circuit_report(&playground, 10, 3);
+        assert_eq!(report.top_product, 40);
+        assert_eq!(report.sizes[..4], [5, 4, 2, 2]);
+        assert_eq!(report.sizes.iter().sum::<usize>(), 20);
+        assert_eq!(report.circuits.len(), 11);
+        let edges: usize = report.circuits.iter().map(|c| c.edges.len()).sum();
+        assert_eq!(edges, 10);
+        for c in &report.circuits {
+            for e in &c.edges {
+                assert!(c.members.contains(&e.box1) && c.members.contains(&e.box2));
+            }
+        }
+    }
+
+    #[test]
+    fn circuit_report_exports() {
+        let playground = Playground {
+            boxes: vec![junction(0, 0, 0), junction(10, 0, 0), junction(0, 3, 4)],
+            connections: 1,
+        };
+        let report = circuit_report(&playground, 1, 2);
+        assert_eq!(report.sizes, vec![2, 1]);
+        assert_eq!(report.top_product, 2);
+        assert_eq!(
+            report_csv(&playground, &report),
+            "circuit,size,members,edges,k,top_product\n\
+             0,2,\"0,0,0 0,3,4\",\"0-2\",2,2\n\
+             1,1,\"10,0,0\",\"\",2,2\n"
+        );
+        let single = circuit_report(&playground, 1, 1);
+        let csv = report_csv(&playground, &single);
+        assert!(
+            csv.lines().skip(1).all(|row| row.ends_with(",1,2")),
+            "{csv}"
+        );
+        assert_eq!(
+            report_json(&playground, &report),
+            "{\"connections\":1,\"k\":2,\"top_product\":2,\"sizes\":[2,1],\"circuits\":[\
+             {\"id\":0,\"size\":2,\"members\":[{\"box\":0,\"x\":0,\"y\":0,\"z\":0},\
+             {\"box\":2,\"x\":0,\"y\":3,\"z\":4}],\"edges\":[{\"box1\":0,\"box2\":2,\
+             \"distance_squared\":25}]},{\"id\":1,\"size\":1,\"members\":[{\"box\":1,\
+             \"x\":10,\"y\":0,\"z\":0}],\"edges\":[]}]}\n"
+        );
+    }
+
+    #[test]
+    fn pop_minimum_distance_in_order() {
+        let boxes = vec![junction(0, 0, 0), junction(10, 0, 0), junction(0, 3, 4)];
+        let mut plan = JunctionPlan::build(&boxes);
+        let first = plan.pop_minimum_distance().unwrap();
+        assert_eq!((first.box1, first.box2, first.weight), (0, 2, 25));
+        let second = plan.pop_minimum_distance().unwrap();
+        assert_eq!((second.box1, second.box2, second.weight), (0, 1, 100));
+        let third = plan.pop_minimum_distance().unwrap();
+        assert_eq!((third.box1, third.box2), (1, 2));
+        assert_eq!(plan.pop_minimum_distance(), None);
+    }
+
+    #[test]
+    fn matrix_pops_duplicate_boxes() {
+        let boxes = vec![junction(1, 1, 1), junction(5, 5, 5), junction(1, 1, 1)];
+        let pairs: Vec<(usize, usize, i64)> = JunctionPlan::build(&boxes)
+            .into_connections()
+            .map(|c| (c.box1, c.box2, c.weight))
+            .collect();
+        assert_eq!(pairs, vec![(0, 2, 0), (0, 1, 48), (1, 2, 48)]);
+        let playground = Playground {
+            boxes,
+            connections: 1,
+        };
+        assert_eq!(
+            largest_circuits_matrix(&playground, 2),
+            Ok(largest_circuits(&playground, 2))
+        );
+        assert_eq!(wall_length_matrix(&playground), wall_length(&playground));
+    }
+
+    #[test]
+    fn pop_minimum_distance_matches_sorted_pairs() {
+        let mut rng = Rng::new(8);
+        for _ in 0..50 {
+            let boxes: Vec<JunctionBox> = (0..rng.range(2, 12))
+                .map(|_| {
+                    junction(
+                        rng.range(0, 1000) as i64,
+                        rng.range(0, 1000) as i64,
+                        rng.range(0, 1000) as i64,
+                    )
+                })
+                .collect();
+            let mut expected = vec![];
+            for i in 0..boxes.len() {
+                for j in i + 1..boxes.len() {
+                    expected.push(boxes[i].distance_squared(&boxes[j]));
+                }
+            }
+            expected.sort_unstable();
+
+            let mut plan = JunctionPlan::build(&boxes);
+            for weight in expected {
+                let c = plan.pop_minimum_distance().unwrap();
+                assert_eq!(c.weight, weight);
+                assert_eq!(boxes[c.box1].distance_squared(&boxes[c.box2]), weight);
+            }
+        }
+    }
+}
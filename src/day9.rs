@@ -0,0 +1,389 @@
+use std::collections::HashSet;
+use std::error::Error;
+
+use crate::compress::{Compressed, SummedArea};
+use crate::geometry::{Point2, Rect};
+use crate::parse::{self as parsing, ParseError};
+use crate::polygon::{self, PolygonError};
+use crate::solution::Solution;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Tile>;
+
+    fn parse(raw: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse(raw)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(largest_rectangle(input)?.to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(largest_inside_rectangle(input)?.to_string())
+    }
+}
+
+/// A red tile on the theater floor.
+pub type Tile = Point2<i64>;
+
+pub fn parse_tile(raw: &str) -> Result<Tile, &'static str> {
+    let (x, y) = raw.split_once(',').ok_or("Tile is missing a ','")?;
+    Ok(Tile {
+        x: x.trim().parse::<i64>().map_err(|_| "Invalid tile x")?,
+        y: y.trim().parse::<i64>().map_err(|_| "Invalid tile y")?,
+    })
+}
+
+pub fn parse(raw: &str) -> Result<Vec<Tile>, ParseError> {
+    parsing::lines(raw, parse_tile)
+}
+
+/// Largest rectangle using any two red tiles as opposite corners.
+pub fn largest_rectangle(tiles: &[Tile]) -> Result<i64, &'static str> {
+    best_rectangle(tiles)
+        .map(|r| r.area())
+        .ok_or("Need at least two red tiles")
+}
+
+/// The rectangle behind `largest_rectangle`, the first found on ties.
+pub fn best_rectangle(tiles: &[Tile]) -> Option<Rect<i64>> {
+    let mut best: Option<Rect<i64>> = None;
+    for (i, tile) in tiles.iter().enumerate() {
+        for other in &tiles[i + 1..] {
+            let rect = Rect::from_corners(*tile, *other);
+            if best.is_none_or(|b| rect.area() > b.area()) {
+                best = Some(rect);
+            }
+        }
+    }
+    best
+}
+
+/// Largest rectangle using two red tiles as opposite corners that only
+/// covers red or green tiles. Consecutive red tiles are joined by a loop of
+/// green tiles, and every tile inside the loop is green too.
+pub fn largest_inside_rectangle(tiles: &[Tile]) -> Result<i64, PolygonError> {
+    Ok(best_inside_rectangle(tiles)?.map_or(0, |r| r.area()))
+}
+
+/// The rectangle behind `largest_inside_rectangle`, the first found on ties.
+pub fn best_inside_rectangle(tiles: &[Tile]) -> Result<Option<Rect<i64>>, PolygonError> {
+    let map = TileMap::build(tiles)?;
+    let mut best: Option<Rect<i64>> = None;
+    for (i, tile) in tiles.iter().enumerate() {
+        for other in &tiles[i + 1..] {
+            let rect = Rect::from_corners(*tile, *other);
+            if best.is_none_or(|b| rect.area() > b.area()) && map.covers(&rect) {
+                best = Some(rect);
+            }
+        }
+    }
+    Ok(best)
+}
+
+/// Draws the floor as in the puzzle text: `#` for red tiles, `O` for the
+/// rectangle, `X` for green tiles when `green` is set and `.` for the rest.
+/// The picture runs from 0,0, or from the bounding box's corner when tiles
+/// lie at negative coordinates, with a margin past the last tile. Floors wider
+/// or taller than `max_cells` are scaled down so one character stands for a
+/// square block of tiles, showing the most important thing in the block.
+pub fn render_ascii(
+    tiles: &[Tile],
+    rect: Option<Rect<i64>>,
+    green: bool,
+    max_cells: usize,
+) -> String {
+    let Some(bounds) = Rect::bounding(tiles) else {
+        return String::new();
+    };
+    let origin = Point2::new(bounds.min.x.min(0), bounds.min.y.min(0));
+    let (width, height) = (bounds.max.x - origin.x + 3, bounds.max.y - origin.y + 2);
+    let scale = (width.max(height) as u64).div_ceil(max_cells.max(1) as u64) as i64;
+    let red: HashSet<(i64, i64)> = tiles
+        .iter()
+        .map(|t| ((t.x - origin.x) / scale, (t.y - origin.y) / scale))
+        .collect();
+
+    let mut out = String::new();
+    if scale > 1 {
+        out.push_str(&format!("Each character is {scale}x{scale} tiles\n"));
+    }
+    let cells = |len: i64| (len as u64).div_ceil(scale as u64) as i64;
+    for row in 0..cells(height) {
+        for col in 0..cells(width) {
+            let (x, y) = (origin.x + col * scale, origin.y + row * scale);
+            let block =
+                Rect::from_corners(Point2::new(x, y), Point2::new(x + scale - 1, y + scale - 1));
+            let c = if rect.is_some_and(|r| r.intersection(&block).is_some()) {
+                'O'
+            } else if red.contains(&(col, row)) {
+                '#'
+            } else if green && polygon::contains(tiles, &block.min) {
+                'X'
+            } else {
+                '.'
+            };
+            out.push(c);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// An SVG picture of the loop, the red tiles and the best rectangle for each
+/// part, with tiles drawn as unit squares.
+pub fn render_svg(tiles: &[Tile], part1: Option<Rect<i64>>, part2: Option<Rect<i64>>) -> String {
+    let Some(bounds) = Rect::bounding(tiles) else {
+        return String::new();
+    };
+    // Keep lines and markers visible however large the floor is.
+    let unit = (bounds.width().max(bounds.height()) / 400).max(1);
+    let pad = unit * 10;
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+        bounds.min.x - pad,
+        bounds.min.y - pad,
+        bounds.width() + 2 * pad,
+        bounds.height() + 2 * pad
+    );
+    let points: Vec<String> = tiles
+        .iter()
+        .map(|t| format!("{},{}", t.x as f64 + 0.5, t.y as f64 + 0.5))
+        .collect();
+    out.push_str(&format!(
+        "  <polygon points=\"{}\" fill=\"#b8e6b8\" stroke=\"#2e8b57\" stroke-width=\"{unit}\"/>\n",
+        points.join(" ")
+    ));
+    for (rect, colour, label) in [(part1, "#1f77b4", "Part 1"), (part2, "#ff7f0e", "Part 2")] {
+        let Some(r) = rect else {
+            continue;
+        };
+        out.push_str(&format!(
+            "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{colour}\" \
+             fill-opacity=\"0.25\" stroke=\"{colour}\" stroke-width=\"{}\">\
+             <title>{label}: {}</title></rect>\n",
+            r.min.x,
+            r.min.y,
+            r.width(),
+            r.height(),
+            unit * 2,
+            r.area()
+        ));
+    }
+    for t in tiles {
+        out.push_str(&format!(
+            "  <rect x=\"{}\" y=\"{}\" width=\"{unit}\" height=\"{unit}\" fill=\"#d62728\"/>\n",
+            t.x, t.y
+        ));
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Which tiles are red or green. The floor is far too big to store tile by
+/// tile, so it is compressed to the rows and columns holding red tiles and
+/// the gaps between them, each of which is all in or all out of the loop.
+pub struct TileMap {
+    xs: Compressed<i64>,
+    ys: Compressed<i64>,
+    // Count of cells outside the loop, so a rectangle is covered when its
+    // count is zero.
+    outside: SummedArea<i64>,
+}
+
+impl TileMap {
+    pub fn build(tiles: &[Tile]) -> Result<TileMap, PolygonError> {
+        polygon::validate(tiles)?;
+        let xs = Compressed::new(tiles.iter().map(|t| t.x), true);
+        let ys = Compressed::new(tiles.iter().map(|t| t.y), true);
+        let column = |x: i64| xs.index(x).unwrap();
+
+        let mut filled = vec![vec![false; xs.len()]; ys.len()];
+        for (row, cells) in filled.iter_mut().enumerate() {
+            let y = ys.span(row).0;
+            let mut crossings = vec![false; xs.len()];
+            for (a, b) in polygon::edges(tiles) {
+                let (low, high) = (a.y.min(b.y), a.y.max(b.y));
+                if low <= y && y <= high {
+                    cells[column(a.x.min(b.x))..=column(a.x.max(b.x))].fill(true);
+                }
+                // Vertical edges count as half open, as in `polygon::contains`.
+                if a.x == b.x && low <= y && y < high {
+                    crossings[column(a.x)] ^= true;
+                }
+            }
+            let mut inside = false;
+            for (cell, crossing) in cells.iter_mut().zip(crossings) {
+                *cell |= inside;
+                inside ^= crossing;
+            }
+        }
+        let outside = SummedArea::build(xs.len(), ys.len(), |x, y| i64::from(!filled[y][x]));
+        Ok(TileMap { xs, ys, outside })
+    }
+
+    /// Whether every tile of `rect` is red or green.
+    pub fn covers(&self, rect: &Rect<i64>) -> bool {
+        let cell = |p: &Point2<i64>| Some((self.xs.index(p.x)?, self.ys.index(p.y)?));
+        match (cell(&rect.min), cell(&rect.max)) {
+            (Some(min), Some(max)) => self.outside.sum(min, max) == 0,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::Rng;
+    use std::fs;
+
+    #[test]
+    fn largest_rectangle_example() {
+        let tiles = parse("7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3\n").unwrap();
+        assert_eq!(Rect::from_corners(tiles[0], tiles[5]).area(), 30);
+        let corners = Rect::from_corners(Tile { x: 2, y: 5 }, Tile { x: 9, y: 7 });
+        assert_eq!(corners.area(), 24);
+        assert_eq!(largest_rectangle(&tiles), Ok(50));
+        assert_eq!(largest_inside_rectangle(&tiles), Ok(24));
+    }
+
+    // The example pictures from the readme.
+    fn readme_picture(heading: &str) -> String {
+        let readme = fs::read_to_string("challenges/day9/readme.md").unwrap();
+        let start = readme.find(heading).unwrap();
+        let lines = readme[start..]
+            .lines()
+            .skip(1)
+            .skip_while(|l| l.trim().is_empty());
+        lines
+            .take_while(|l| l.starts_with('.'))
+            .map(|l| format!("{l}\n"))
+            .collect()
+    }
+
+    #[test]
+    fn render_ascii_matches_readme() {
+        let tiles = parse(&fs::read_to_string("challenges/day9/test.txt").unwrap()).unwrap();
+        assert_eq!(
+            render_ascii(&tiles, None, false, 100),
+            readme_picture("would look like this:")
+        );
+        let rect = Rect::from_corners(Point2::new(2, 5), Point2::new(9, 7));
+        assert_eq!(
+            render_ascii(&tiles, Some(rect), false, 100),
+            readme_picture("with an area of 24 between 2,5 and 9,7:")
+        );
+        assert_eq!(
+            render_ascii(&tiles, best_rectangle(&tiles), false, 100),
+            readme_picture("One way to do this is between 2,5 and 11,1:")
+        );
+    }
+
+    #[test]
+    fn render_ascii_shows_green_and_scales() {
+        let tiles = parse(&fs::read_to_string("challenges/day9/test.txt").unwrap()).unwrap();
+        let picture = render_ascii(&tiles, None, true, 100);
+        assert_eq!(picture.lines().nth(3), Some("..#XXXX#XXXX.."));
+        let small = render_ascii(&tiles, None, false, 7);
+        let rows: Vec<&str> = small.lines().collect();
+        assert_eq!(rows[0], "Each character is 2x2 tiles");
+        assert_eq!(
+            rows[1..],
+            ["...#.#.", ".#.#...", ".#..#..", "....##.", "......."]
+        );
+    }
+
+    #[test]
+    fn render_handles_negative_coordinates() {
+        let tiles: Vec<Tile> = [(-3, -2), (-1, -2), (-1, 0), (-3, 0)]
+            .into_iter()
+            .map(|(x, y)| Point2::new(x, y))
+            .collect();
+        assert_eq!(
+            render_ascii(&tiles, None, true, 100),
+            "#X#..\nXXX..\n#X#..\n.....\n"
+        );
+        let svg = render_svg(&tiles, None, None);
+        assert!(svg.contains("points=\"-2.5,-1.5 -0.5,-1.5 -0.5,0.5 -2.5,0.5\""));
+    }
+
+    #[test]
+    fn render_svg_draws_everything() {
+        let tiles = parse(&fs::read_to_string("challenges/day9/test.txt").unwrap()).unwrap();
+        let svg = render_svg(
+            &tiles,
+            best_rectangle(&tiles),
+            best_inside_rectangle(&tiles).unwrap(),
+        );
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<polygon").count(), 1);
+        assert_eq!(svg.matches("<rect").count(), tiles.len() + 2);
+        assert!(svg.contains("<title>Part 1: 50</title>"));
+        assert!(svg.contains("<title>Part 2: 24</title>"));
+    }
+
+    // Red and green tiles found one by one: the loop's own tiles, then
+    // everything not reachable from outside without crossing them.
+    fn brute_tiles(tiles: &[Tile], size: i64) -> HashSet<(i64, i64)> {
+        let mut boundary = HashSet::new();
+        for (a, b) in polygon::edges(tiles) {
+            let edge = Rect::from_corners(a, b);
+            for x in edge.min.x..=edge.max.x {
+                for y in edge.min.y..=edge.max.y {
+                    boundary.insert((x, y));
+                }
+            }
+        }
+        let mut outside = HashSet::from([(-1, -1)]);
+        let mut stack = vec![(-1, -1)];
+        while let Some((x, y)) = stack.pop() {
+            for next in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+                let on_grid =
+                    (-1..=size + 1).contains(&next.0) && (-1..=size + 1).contains(&next.1);
+                if on_grid && !boundary.contains(&next) && outside.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        let mut covered = HashSet::new();
+        for x in 0..=size {
+            for y in 0..=size {
+                if !outside.contains(&(x, y)) {
+                    covered.insert((x, y));
+                }
+            }
+        }
+        covered
+    }
+
+    #[test]
+    fn tile_map_matches_tile_by_tile() {
+        let mut rng = Rng::new(9);
+        for _ in 0..100 {
+            let tiles = polygon::tests::random_loop(&mut rng);
+            let covered = brute_tiles(&tiles, 10);
+            let map = TileMap::build(&tiles).unwrap();
+            for a in &tiles {
+                for b in &tiles {
+                    let rect = Rect::from_corners(*a, *b);
+                    let brute = (rect.min.x..=rect.max.x)
+                        .all(|x| (rect.min.y..=rect.max.y).all(|y| covered.contains(&(x, y))));
+                    assert_eq!(map.covers(&rect), brute, "{rect:?} in {tiles:?}");
+                    assert_eq!(polygon::contains_rect(&tiles, &rect), brute);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn adjacent_edges_leave_no_gap() {
+        let tiles = parse("0,0\n5,0\n5,3\n6,3\n6,0\n10,0\n10,5\n0,5\n").unwrap();
+        let rect = Rect::from_corners(tiles[0], tiles[6]);
+        assert!(TileMap::build(&tiles).unwrap().covers(&rect));
+        assert!(polygon::contains_rect(&tiles, &rect));
+        assert_eq!(largest_inside_rectangle(&tiles), Ok(66));
+    }
+}
@@ -0,0 +1,162 @@
+use std::time::Duration;
+
+use crate::runner::format_duration;
+use crate::solution::{self, PartRun};
+
+/// Every registered implementation of one part, run on the same input.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Comparison {
+    pub day: u32,
+    pub part: u32,
+    pub runs: Vec<(&'static str, Result<PartRun, String>)>,
+}
+
+impl Comparison {
+    /// The distinct answers among the implementations that produced one.
+    /// Implementations that failed or gave up are left out.
+    pub fn answers(&self) -> Vec<&str> {
+        let mut answers: Vec<&str> = vec![];
+        for (_, run) in &self.runs {
+            if let Ok(PartRun { answer: Ok(a), .. }) = run
+                && !answers.contains(&a.as_str())
+            {
+                answers.push(a);
+            }
+        }
+        answers
+    }
+
+    pub fn agrees(&self) -> bool {
+        self.answers().len() <= 1
+    }
+
+    // The quickest time among implementations that gave an answer.
+    fn fastest(&self) -> Option<Duration> {
+        self.runs
+            .iter()
+            .filter_map(|(_, run)| match run {
+                Ok(PartRun {
+                    answer: Ok(_),
+                    elapsed,
+                }) => Some(*elapsed),
+                _ => None,
+            })
+            .min()
+    }
+}
+
+/// Runs every implementation registered for `day` on `raw`, one comparison
+/// per part that has more than one.
+pub fn compare(day: u32, raw: &str) -> Vec<Comparison> {
+    let mut comparisons = vec![];
+    for part in [1, 2] {
+        let implementations = solution::implementations(day, part);
+        if implementations.len() < 2 {
+            continue;
+        }
+        let runs = implementations
+            .iter()
+            .map(|i| (i.name, (i.run)(raw)))
+            .collect();
+        comparisons.push(Comparison { day, part, runs });
+    }
+    comparisons
+}
+
+/// One block per comparison: whether the implementations agree, then each
+/// one's answer, time and time relative to the fastest.
+pub fn report(comparisons: &[Comparison]) -> String {
+    let mut out = String::new();
+    for c in comparisons {
+        let verdict = if c.agrees() { "agree" } else { "MISMATCH" };
+        out.push_str(&format!("Day {} part {}: {verdict}\n", c.day, c.part));
+        let name_width = c.runs.iter().map(|(n, _)| n.len()).max().unwrap_or(0);
+        let fastest = c.fastest();
+        for (name, run) in &c.runs {
+            let detail = match run {
+                Ok(PartRun {
+                    answer: Ok(a),
+                    elapsed,
+                }) => {
+                    let relative = fastest
+                        .filter(|f| !f.is_zero())
+                        .map_or(1.0, |f| elapsed.as_secs_f64() / f.as_secs_f64());
+                    format!("{a} in {} ({relative:.2}x)", format_duration(*elapsed))
+                }
+                Ok(PartRun { answer: Err(e), .. }) => format!("error: {e}"),
+                Err(e) => format!("parse error: {e}"),
+            };
+            out.push_str(&format!("  {name:<name_width$}  {detail}\n"));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::{self, Knobs};
+
+    fn run(answer: Result<&str, &str>, millis: u64) -> Result<PartRun, String> {
+        Ok(PartRun {
+            answer: answer.map(String::from).map_err(String::from),
+            elapsed: Duration::from_millis(millis),
+        })
+    }
+
+    #[test]
+    fn agreement_ignores_failures() {
+        let c = Comparison {
+            day: 5,
+            part: 2,
+            runs: vec![
+                ("fast", run(Ok("14"), 2)),
+                ("slow", run(Err("Too big"), 1)),
+                ("broken", Err("Bad input".to_string())),
+            ],
+        };
+        assert_eq!(c.answers(), vec!["14"]);
+        assert!(c.agrees());
+        assert_eq!(
+            report(&[c]),
+            "Day 5 part 2: agree\n  \
+             fast    14 in 2.00ms (1.00x)\n  \
+             slow    error: Too big\n  \
+             broken  parse error: Bad input\n"
+        );
+    }
+
+    #[test]
+    fn report_flags_mismatches() {
+        let c = Comparison {
+            day: 8,
+            part: 1,
+            runs: vec![("a", run(Ok("40"), 1)), ("b", run(Ok("41"), 3))],
+        };
+        assert!(!c.agrees());
+        assert_eq!(
+            report(&[c]),
+            "Day 8 part 1: MISMATCH\n  a  40 in 1.00ms (1.00x)\n  b  41 in 3.00ms (3.00x)\n"
+        );
+    }
+
+    #[test]
+    fn registered_implementations_agree_on_generated_inputs() {
+        for day in [2, 5, 8] {
+            for seed in 0..3 {
+                let knobs = Knobs {
+                    size: 30,
+                    width: 20,
+                    seed,
+                };
+                let raw = generate::generate(day, knobs).unwrap();
+                let comparisons = compare(day, &raw);
+                assert_eq!(comparisons.len(), 2);
+                for c in comparisons {
+                    assert_eq!(c.answers().len(), 1, "{}", report(&[c]));
+                }
+            }
+        }
+        assert!(compare(1, "L1\n").is_empty());
+    }
+}
@@ -0,0 +1,320 @@
+use std::fmt::Write;
+
+use crate::util::Rng;
+
+/// Size knobs for a generated input. What they control depends on the day:
+/// `size` is the number of lines, ranges, problems, points or machines and
+/// `width` the length of a line where a day has one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Knobs {
+    pub size: usize,
+    pub width: usize,
+    pub seed: u64,
+}
+
+impl Default for Knobs {
+    fn default() -> Knobs {
+        Knobs {
+            size: 100,
+            width: 100,
+            seed: 1,
+        }
+    }
+}
+
+const NO_GENERATOR: &str = "No generator for that day";
+
+/// Produces a random input in the format of the given day.
+pub fn generate(day: u32, knobs: Knobs) -> Result<String, &'static str> {
+    if knobs.size == 0 || knobs.width == 0 {
+        return Err("Size and width must be at least 1");
+    }
+    let mut rng = Rng::new(knobs.seed);
+    let input = match day {
+        1 => rotations(&mut rng, knobs),
+        2 => id_ranges(&mut rng, knobs),
+        3 => battery_banks(&mut rng, knobs),
+        4 => floor_grid(&mut rng, knobs),
+        5 => inventory(&mut rng, knobs),
+        6 => worksheet(&mut rng, knobs),
+        7 => manifold(&mut rng, knobs),
+        8 => junction_boxes(&mut rng, knobs),
+        9 => red_tiles(&mut rng, knobs),
+        10 => machines(&mut rng, knobs),
+        _ => return Err(NO_GENERATOR),
+    };
+    Ok(input)
+}
+
+fn rotations(rng: &mut Rng, knobs: Knobs) -> String {
+    let mut out = String::new();
+    for _ in 0..knobs.size {
+        let dir = if rng.chance(1, 2) { 'L' } else { 'R' };
+        writeln!(out, "{dir}{}", rng.range(1, 999)).unwrap();
+    }
+    out
+}
+
+// Ranges are kept short since the solver walks every id in them.
+fn id_ranges(rng: &mut Rng, knobs: Knobs) -> String {
+    let ranges: Vec<String> = (0..knobs.size)
+        .map(|_| {
+            let digits = rng.range(1, 10) as u32;
+            let low = rng.range(10u64.pow(digits - 1), 10u64.pow(digits) - 1);
+            format!("{low}-{}", low + rng.range(0, 10_000))
+        })
+        .collect();
+    ranges.join(",") + "\n"
+}
+
+fn battery_banks(rng: &mut Rng, knobs: Knobs) -> String {
+    let mut out = String::new();
+    for _ in 0..knobs.size {
+        // Part 2 keeps twelve batteries, so banks need at least that many.
+        for _ in 0..knobs.width.max(12) {
+            write!(out, "{}", rng.range(1, 9)).unwrap();
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn floor_grid(rng: &mut Rng, knobs: Knobs) -> String {
+    let mut out = String::new();
+    for _ in 0..knobs.size {
+        for _ in 0..knobs.width {
+            out.push(if rng.chance(2, 3) { '@' } else { '.' });
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn inventory(rng: &mut Rng, knobs: Knobs) -> String {
+    const LIMIT: u64 = 1_000_000_000_000;
+    let mut out = String::new();
+    for _ in 0..knobs.size {
+        let low = rng.range(1, LIMIT);
+        writeln!(out, "{low}-{}", low + rng.range(0, LIMIT / 100)).unwrap();
+    }
+    out.push('\n');
+    for _ in 0..knobs.size {
+        writeln!(out, "{}", rng.range(1, LIMIT)).unwrap();
+    }
+    out
+}
+
+// Four rows of numbers with each problem padded to its widest number and the
+// numbers aligned left or right at random, as in the real worksheets.
+fn worksheet(rng: &mut Rng, knobs: Knobs) -> String {
+    let mut rows = vec![String::new(); 5];
+    for problem in 0..knobs.size {
+        let numbers: Vec<String> = (0..4)
+            .map(|_| {
+                let digits = rng.range(1, 4) as u32;
+                rng.range(1, 10u64.pow(digits) - 1).to_string()
+            })
+            .collect();
+        let width = numbers.iter().map(|n| n.len()).max().unwrap();
+        let left = rng.chance(1, 2);
+        for (row, n) in rows.iter_mut().zip(&numbers) {
+            if problem > 0 {
+                row.push(' ');
+            }
+            if left {
+                write!(row, "{n:<width$}").unwrap();
+            } else {
+                write!(row, "{n:>width$}").unwrap();
+            }
+        }
+        if problem > 0 {
+            rows[4].push(' ');
+        }
+        let operator = if rng.chance(1, 2) { '+' } else { '*' };
+        write!(rows[4], "{operator:<width$}").unwrap();
+    }
+    rows.join("\n") + "\n"
+}
+
+// Splitters only appear on every other row, like the puzzle's manifolds.
+fn manifold(rng: &mut Rng, knobs: Knobs) -> String {
+    let mut out = String::new();
+    let start = knobs.width / 2;
+    for row in 0..knobs.size {
+        for col in 0..knobs.width {
+            out.push(match row {
+                0 if col == start => 'S',
+                r if r % 2 == 0 && r > 0 && rng.chance(1, 4) => '^',
+                _ => '.',
+            });
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn junction_boxes(rng: &mut Rng, knobs: Knobs) -> String {
+    let mut out = String::new();
+    for _ in 0..knobs.size.max(2) {
+        let [x, y, z] = [
+            rng.range(0, 99_999),
+            rng.range(0, 99_999),
+            rng.range(0, 99_999),
+        ];
+        writeln!(out, "{x},{y},{z}").unwrap();
+    }
+    out
+}
+
+// Builds a simple rectilinear loop from a random skyline along the top and a
+// random skyline along the bottom, which can never cross each other.
+fn red_tiles(rng: &mut Rng, knobs: Knobs) -> String {
+    const SPAN: u64 = 100_000;
+    const MIDDLE: u64 = SPAN / 2;
+    let steps = (knobs.size / 4).max(1);
+    let breakpoints = |rng: &mut Rng| -> Vec<u64> {
+        let mut xs: Vec<u64> = (0..steps - 1).map(|_| rng.range(1, SPAN - 1)).collect();
+        xs.push(0);
+        xs.push(SPAN);
+        xs.sort_unstable();
+        xs.dedup();
+        xs
+    };
+    let heights = |rng: &mut Rng, count: usize, low: u64, high: u64| -> Vec<u64> {
+        let mut hs: Vec<u64> = vec![];
+        while hs.len() < count {
+            let h = rng.range(low, high);
+            if hs.last() != Some(&h) {
+                hs.push(h);
+            }
+        }
+        hs
+    };
+
+    let top_x = breakpoints(rng);
+    let top_y = heights(rng, top_x.len() - 1, MIDDLE + 1, SPAN);
+    let bottom_x = breakpoints(rng);
+    let bottom_y = heights(rng, bottom_x.len() - 1, 0, MIDDLE - 1);
+
+    let mut tiles = vec![];
+    for (i, y) in top_y.iter().enumerate() {
+        tiles.push((top_x[i], *y));
+        tiles.push((top_x[i + 1], *y));
+    }
+    for (i, y) in bottom_y.iter().enumerate().rev() {
+        tiles.push((bottom_x[i + 1], *y));
+        tiles.push((bottom_x[i], *y));
+    }
+    let mut out = String::new();
+    for (x, y) in tiles {
+        writeln!(out, "{x},{y}").unwrap();
+    }
+    out
+}
+
+// Targets and joltages are built from actual button presses so every
+// generated machine can be solved.
+fn machines(rng: &mut Rng, knobs: Knobs) -> String {
+    let mut out = String::new();
+    for _ in 0..knobs.size {
+        let lights = rng.range(3, 10) as usize;
+        let buttons: Vec<Vec<usize>> = (0..rng.range(3, 13))
+            .map(|_| {
+                let mut wiring: Vec<usize> = (0..lights).filter(|_| rng.chance(2, 5)).collect();
+                if wiring.is_empty() {
+                    wiring.push(rng.range(0, lights as u64 - 1) as usize);
+                }
+                wiring
+            })
+            .collect();
+
+        let mut state = vec![false; lights];
+        let mut joltage = vec![0; lights];
+        for wiring in &buttons {
+            let presses = rng.range(0, 20);
+            for light in wiring {
+                state[*light] ^= presses % 2 == 1;
+                joltage[*light] += presses;
+            }
+        }
+
+        out.push('[');
+        out.extend(state.iter().map(|on| if *on { '#' } else { '.' }));
+        out.push(']');
+        for wiring in &buttons {
+            let wiring: Vec<String> = wiring.iter().map(|l| l.to_string()).collect();
+            write!(out, " ({})", wiring.join(",")).unwrap();
+        }
+        let joltage: Vec<String> = joltage.iter().map(|j| j.to_string()).collect();
+        writeln!(out, " {{{}}}", joltage.join(",")).unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Point2;
+    use crate::polygon;
+    use crate::solution;
+
+    #[test]
+    fn generated_inputs_solve() {
+        let knobs = Knobs {
+            size: 40,
+            width: 30,
+            seed: 9,
+        };
+        for day in solution::DAYS {
+            let input = match generate(day.day, knobs) {
+                Ok(input) => input,
+                // Days scaffolded with `new` have no generator until one is written.
+                Err(NO_GENERATOR) => continue,
+                Err(e) => panic!("day {}: {e}", day.day),
+            };
+            let answers = (day.run)(&input).unwrap();
+            assert!(
+                answers.part1.is_ok(),
+                "day {}: {:?}",
+                day.day,
+                answers.part1
+            );
+            let unsolved = Err("Part 2 is not solved yet".to_string());
+            assert!(
+                answers.part2.is_ok() || answers.part2 == unsolved,
+                "day {}: {:?}",
+                day.day,
+                answers.part2
+            );
+        }
+    }
+
+    #[test]
+    fn generate_is_repeatable() {
+        let knobs = Knobs::default();
+        assert_eq!(generate(6, knobs), generate(6, knobs));
+        let other = Knobs { seed: 2, ..knobs };
+        assert_ne!(generate(6, knobs), generate(6, other));
+    }
+
+    #[test]
+    fn red_tiles_form_a_rectilinear_loop() {
+        let input = generate(9, Knobs::default()).unwrap();
+        let tiles: Vec<(u64, u64)> = input
+            .lines()
+            .map(|l| {
+                let (x, y) = l.split_once(',').unwrap();
+                (x.parse().unwrap(), y.parse().unwrap())
+            })
+            .collect();
+        for (i, a) in tiles.iter().enumerate() {
+            let b = tiles[(i + 1) % tiles.len()];
+            assert!((a.0 == b.0) != (a.1 == b.1), "{a:?} -> {b:?}");
+        }
+        let corners: Vec<Point2<i64>> = tiles
+            .iter()
+            .map(|(x, y)| Point2::new(*x as i64, *y as i64))
+            .collect();
+        assert_eq!(polygon::validate(&corners), Ok(()));
+    }
+}
@@ -0,0 +1,265 @@
+use std::ops::{Add, Mul, Sub};
+
+use crate::num::Integer;
+
+/// A point or offset on a 2D grid.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A point or offset in 3D space.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+// Distance between two coordinates, taken in an order that cannot underflow
+// unsigned types.
+fn gap<T: Integer>(a: T, b: T) -> T {
+    if a > b { a - b } else { b - a }
+}
+
+impl<T: Integer> Point2<T> {
+    pub fn new(x: T, y: T) -> Point2<T> {
+        Point2 { x, y }
+    }
+
+    /// Squared straight line distance, which orders points the same way as
+    /// the true distance without leaving the integers.
+    pub fn distance_squared(&self, other: &Point2<T>) -> T {
+        let (dx, dy) = (gap(self.x, other.x), gap(self.y, other.y));
+        dx * dx + dy * dy
+    }
+
+    pub fn manhattan(&self, other: &Point2<T>) -> T {
+        gap(self.x, other.x) + gap(self.y, other.y)
+    }
+
+    pub fn chebyshev(&self, other: &Point2<T>) -> T {
+        gap(self.x, other.x).max(gap(self.y, other.y))
+    }
+}
+
+impl<T: Integer> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Point3<T> {
+        Point3 { x, y, z }
+    }
+
+    /// Squared straight line distance, which orders points the same way as
+    /// the true distance without leaving the integers.
+    pub fn distance_squared(&self, other: &Point3<T>) -> T {
+        let (dx, dy, dz) = (
+            gap(self.x, other.x),
+            gap(self.y, other.y),
+            gap(self.z, other.z),
+        );
+        dx * dx + dy * dy + dz * dz
+    }
+
+    pub fn manhattan(&self, other: &Point3<T>) -> T {
+        gap(self.x, other.x) + gap(self.y, other.y) + gap(self.z, other.z)
+    }
+
+    pub fn chebyshev(&self, other: &Point3<T>) -> T {
+        gap(self.x, other.x)
+            .max(gap(self.y, other.y))
+            .max(gap(self.z, other.z))
+    }
+
+    /// The smallest and largest corner of the box around `points`, `None`
+    /// when there are no points.
+    pub fn bounding_box(points: &[Point3<T>]) -> Option<(Point3<T>, Point3<T>)> {
+        let first = *points.first()?;
+        Some(points.iter().fold((first, first), |(low, high), p| {
+            (
+                Point3::new(low.x.min(p.x), low.y.min(p.y), low.z.min(p.z)),
+                Point3::new(high.x.max(p.x), high.y.max(p.y), high.z.max(p.z)),
+            )
+        }))
+    }
+}
+
+impl<T: Integer> Add for Point2<T> {
+    type Output = Point2<T>;
+    fn add(self, other: Point2<T>) -> Point2<T> {
+        Point2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Integer> Sub for Point2<T> {
+    type Output = Point2<T>;
+    fn sub(self, other: Point2<T>) -> Point2<T> {
+        Point2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Integer> Mul<T> for Point2<T> {
+    type Output = Point2<T>;
+    fn mul(self, scale: T) -> Point2<T> {
+        Point2::new(self.x * scale, self.y * scale)
+    }
+}
+
+impl<T: Integer> Add for Point3<T> {
+    type Output = Point3<T>;
+    fn add(self, other: Point3<T>) -> Point3<T> {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Integer> Sub for Point3<T> {
+    type Output = Point3<T>;
+    fn sub(self, other: Point3<T>) -> Point3<T> {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T: Integer> Mul<T> for Point3<T> {
+    type Output = Point3<T>;
+    fn mul(self, scale: T) -> Point3<T> {
+        Point3::new(self.x * scale, self.y * scale, self.z * scale)
+    }
+}
+
+/// An axis-aligned rectangle of tiles. Both corners are part of it, so a
+/// rectangle from (2, 5) to (9, 7) is 8 tiles wide and 3 tall.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Rect<T> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+impl<T: Integer> Rect<T> {
+    /// The rectangle with `a` and `b` as opposite corners, in either order.
+    pub fn from_corners(a: Point2<T>, b: Point2<T>) -> Rect<T> {
+        Rect {
+            min: Point2::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Point2::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    /// The smallest rectangle holding every point, `None` when there are no
+    /// points.
+    pub fn bounding(points: &[Point2<T>]) -> Option<Rect<T>> {
+        let first = *points.first()?;
+        Some(
+            points
+                .iter()
+                .fold(Rect::from_corners(first, first), |r, p| Rect {
+                    min: Point2::new(r.min.x.min(p.x), r.min.y.min(p.y)),
+                    max: Point2::new(r.max.x.max(p.x), r.max.y.max(p.y)),
+                }),
+        )
+    }
+
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::ONE
+    }
+
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::ONE
+    }
+
+    /// Number of tiles covered, corners included.
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, p: &Point2<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    pub fn contains_rect(&self, other: &Rect<T>) -> bool {
+        self.contains(&other.min) && self.contains(&other.max)
+    }
+
+    /// The tiles both rectangles cover, `None` if they share none.
+    pub fn intersection(&self, other: &Rect<T>) -> Option<Rect<T>> {
+        let min = Point2::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y));
+        let max = Point2::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y));
+        (min.x <= max.x && min.y <= max.y).then_some(Rect { min, max })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::Rng;
+
+    #[test]
+    fn point_ops_and_distances() {
+        let a = Point2::new(1i64, -2);
+        let b = Point2::new(4, 2);
+        assert_eq!(a + b, Point2::new(5, 0));
+        assert_eq!(b - a, Point2::new(3, 4));
+        assert_eq!(a * 3, Point2::new(3, -6));
+        assert_eq!(a.distance_squared(&b), 25);
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+
+        let p = Point3::new(0u32, 0, 0);
+        let q = Point3::new(2, 3, 6);
+        assert_eq!(q - p, q);
+        assert_eq!(p.manhattan(&q), q.manhattan(&p));
+        assert_eq!(p.distance_squared(&q), 49);
+        assert_eq!(p.manhattan(&q), 11);
+        assert_eq!(q.chebyshev(&p), 6);
+    }
+
+    #[test]
+    fn bounding_boxes() {
+        let points = [Point3::new(1, 5, -2), Point3::new(-3, 2, 4)];
+        assert_eq!(
+            Point3::bounding_box(&points),
+            Some((Point3::new(-3, 2, -2), Point3::new(1, 5, 4)))
+        );
+        assert_eq!(Point3::<i32>::bounding_box(&[]), None);
+        let tiles = [Point2::new(7, 1), Point2::new(11, 7), Point2::new(2, 5)];
+        assert_eq!(
+            Rect::bounding(&tiles),
+            Some(Rect::from_corners(Point2::new(2, 1), Point2::new(11, 7)))
+        );
+    }
+
+    #[test]
+    fn rect_area_counts_tiles() {
+        let r = Rect::from_corners(Point2::new(9, 7), Point2::new(2, 5));
+        assert_eq!((r.width(), r.height(), r.area()), (8, 3, 24));
+        assert_eq!(
+            Rect::from_corners(Point2::new(3, 3), Point2::new(3, 3)).area(),
+            1
+        );
+    }
+
+    #[test]
+    fn rect_intersection_and_containment() {
+        let r = |x1, y1, x2, y2| Rect::from_corners(Point2::new(x1, y1), Point2::new(x2, y2));
+        let a = r(0, 0, 5, 5);
+        assert_eq!(a.intersection(&r(3, 4, 8, 9)), Some(r(3, 4, 5, 5)));
+        assert_eq!(a.intersection(&r(5, 5, 6, 6)), Some(r(5, 5, 5, 5)));
+        assert_eq!(a.intersection(&r(6, 0, 7, 5)), None);
+        assert!(a.contains(&Point2::new(5, 0)));
+        assert!(!a.contains(&Point2::new(6, 0)));
+        assert!(a.contains_rect(&r(1, 1, 5, 5)));
+        assert!(!a.contains_rect(&r(1, 1, 6, 5)));
+    }
+
+    #[test]
+    fn intersection_matches_tile_count() {
+        let mut rng = Rng::new(5);
+        let mut corner = || Point2::new(rng.range(0, 12) as i32, rng.range(0, 12) as i32);
+        for _ in 0..300 {
+            let a = Rect::from_corners(corner(), corner());
+            let b = Rect::from_corners(corner(), corner());
+            let shared = (0..13)
+                .flat_map(|x| (0..13).map(move |y| Point2::new(x, y)))
+                .filter(|p| a.contains(p) && b.contains(p))
+                .count() as i32;
+            assert_eq!(a.intersection(&b).map_or(0, |r| r.area()), shared);
+        }
+    }
+}
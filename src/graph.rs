@@ -0,0 +1,412 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Anything that can list the weighted edges leaving a node. Searches only
+/// ever ask for the neighbours of nodes they reach, so a graph can be far too
+/// big to build up front.
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    fn neighbours(&self, node: &Self::Node) -> Vec<(Self::Node, u64)>;
+}
+
+/// A graph over nodes `0..len` stored as a list of outgoing edges per node.
+#[derive(Debug, Clone, Default)]
+pub struct AdjacencyList {
+    edges: Vec<Vec<(usize, u64)>>,
+}
+
+impl AdjacencyList {
+    pub fn new(len: usize) -> AdjacencyList {
+        AdjacencyList {
+            edges: vec![vec![]; len],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: u64) {
+        self.edges[from].push((to, weight));
+    }
+
+    /// Adds the edge in both directions.
+    pub fn add_undirected(&mut self, a: usize, b: usize, weight: u64) {
+        self.add_edge(a, b, weight);
+        self.add_edge(b, a, weight);
+    }
+}
+
+impl Graph for AdjacencyList {
+    type Node = usize;
+
+    fn neighbours(&self, node: &usize) -> Vec<(usize, u64)> {
+        self.edges[*node].clone()
+    }
+}
+
+/// A graph whose edges come from a closure, for state spaces that are only
+/// explored as far as a search needs.
+pub struct Implicit<N, F> {
+    neighbours: F,
+    node: std::marker::PhantomData<N>,
+}
+
+pub fn implicit<N, F>(neighbours: F) -> Implicit<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> Vec<(N, u64)>,
+{
+    Implicit {
+        neighbours,
+        node: std::marker::PhantomData,
+    }
+}
+
+impl<N, F> Graph for Implicit<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> Vec<(N, u64)>,
+{
+    type Node = N;
+
+    fn neighbours(&self, node: &N) -> Vec<(N, u64)> {
+        (self.neighbours)(node)
+    }
+}
+
+/// A route found by a search, from the start to the goal inclusive.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Path<N> {
+    pub nodes: Vec<N>,
+    pub cost: u64,
+}
+
+// Walks parent links back from `end` to rebuild the route.
+fn rebuild<N: Clone>(nodes: &[N], parent: &[Option<usize>], end: usize, cost: u64) -> Path<N> {
+    let mut route = vec![nodes[end].clone()];
+    let mut at = end;
+    while let Some(p) = parent[at] {
+        route.push(nodes[p].clone());
+        at = p;
+    }
+    route.reverse();
+    Path { nodes: route, cost }
+}
+
+/// Fewest edges from `start` to a node passing `is_goal`, ignoring weights.
+/// The path's cost is its number of edges.
+pub fn bfs<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    mut is_goal: impl FnMut(&G::Node) -> bool,
+) -> Option<Path<G::Node>> {
+    let mut seen: HashMap<G::Node, usize> = HashMap::from([(start.clone(), 0)]);
+    let mut nodes = vec![start];
+    let mut parent = vec![None];
+    let mut depth = vec![0];
+    let mut queue = VecDeque::from([0]);
+    while let Some(id) = queue.pop_front() {
+        if is_goal(&nodes[id]) {
+            return Some(rebuild(&nodes, &parent, id, depth[id]));
+        }
+        for (next, _) in graph.neighbours(&nodes[id]) {
+            if seen.contains_key(&next) {
+                continue;
+            }
+            seen.insert(next.clone(), nodes.len());
+            queue.push_back(nodes.len());
+            nodes.push(next);
+            parent.push(Some(id));
+            depth.push(depth[id] + 1);
+        }
+    }
+    None
+}
+
+/// Cheapest path from `start` to a node passing `is_goal`.
+pub fn dijkstra<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl FnMut(&G::Node) -> bool,
+) -> Option<Path<G::Node>> {
+    astar(graph, start, is_goal, |_| 0)
+}
+
+/// Cheapest path from `start` to a node passing `is_goal`, exploring nodes
+/// with the lowest cost so far plus `heuristic` first. The heuristic must
+/// never overestimate the remaining cost or the path may not be cheapest.
+/// A node already explored is explored again when a cheaper path to it turns
+/// up, which only happens when the heuristic is not consistent.
+pub fn astar<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    mut is_goal: impl FnMut(&G::Node) -> bool,
+    mut heuristic: impl FnMut(&G::Node) -> u64,
+) -> Option<Path<G::Node>> {
+    // Nodes are numbered as they are found so the heap and parent links
+    // don't need the node type to be ordered or cheap to copy.
+    let mut ids: HashMap<G::Node, usize> = HashMap::from([(start.clone(), 0)]);
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0, 0))]);
+    let mut nodes = vec![start];
+    let mut parent = vec![None];
+    let mut cost = vec![0];
+    let mut done = vec![false];
+    while let Some(Reverse((_, so_far, id))) = heap.pop() {
+        // Entries left behind by a cheaper path found later are stale.
+        if done[id] || so_far > cost[id] {
+            continue;
+        }
+        done[id] = true;
+        if is_goal(&nodes[id]) {
+            return Some(rebuild(&nodes, &parent, id, so_far));
+        }
+        for (next, weight) in graph.neighbours(&nodes[id]) {
+            let total = so_far + weight;
+            let next_id = match ids.get(&next) {
+                Some(n) if cost[*n] <= total => continue,
+                Some(n) => {
+                    done[*n] = false;
+                    *n
+                }
+                None => {
+                    ids.insert(next.clone(), nodes.len());
+                    nodes.push(next);
+                    parent.push(None);
+                    cost.push(total);
+                    done.push(false);
+                    nodes.len() - 1
+                }
+            };
+            cost[next_id] = total;
+            parent[next_id] = Some(id);
+            heap.push(Reverse((
+                total + heuristic(&nodes[next_id]),
+                total,
+                next_id,
+            )));
+        }
+    }
+    None
+}
+
+/// Groups the nodes of `graph` joined by edges in either direction, each
+/// group in ascending order and the groups ordered by their first node.
+pub fn connected_components(graph: &AdjacencyList) -> Vec<Vec<usize>> {
+    let mut parent: Vec<usize> = (0..graph.len()).collect();
+    fn root(parent: &mut [usize], mut n: usize) -> usize {
+        while parent[n] != n {
+            parent[n] = parent[parent[n]];
+            n = parent[n];
+        }
+        n
+    }
+    for (from, edges) in graph.edges.iter().enumerate() {
+        for (to, _) in edges {
+            let (a, b) = (root(&mut parent, from), root(&mut parent, *to));
+            parent[a.max(b)] = a.min(b);
+        }
+    }
+    let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+    for n in 0..graph.len() {
+        groups.entry(root(&mut parent, n)).or_default().push(n);
+    }
+    let mut components: Vec<Vec<usize>> = groups.into_values().collect();
+    components.sort_unstable_by_key(|c| c[0]);
+    components
+}
+
+/// Orders the nodes so every edge points forwards, picking the smallest
+/// available node first. On a cycle, returns the nodes that could not be
+/// placed.
+pub fn topological_sort(graph: &AdjacencyList) -> Result<Vec<usize>, Vec<usize>> {
+    let mut incoming = vec![0; graph.len()];
+    for edges in &graph.edges {
+        for (to, _) in edges {
+            incoming[*to] += 1;
+        }
+    }
+    let mut ready: BinaryHeap<Reverse<usize>> = (0..graph.len())
+        .filter(|n| incoming[*n] == 0)
+        .map(Reverse)
+        .collect();
+    let mut order = vec![];
+    while let Some(Reverse(n)) = ready.pop() {
+        order.push(n);
+        for (to, _) in &graph.edges[n] {
+            incoming[*to] -= 1;
+            if incoming[*to] == 0 {
+                ready.push(Reverse(*to));
+            }
+        }
+    }
+    if order.len() < graph.len() {
+        return Err((0..graph.len()).filter(|n| incoming[*n] > 0).collect());
+    }
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::Rng;
+
+    fn sample() -> AdjacencyList {
+        let mut g = AdjacencyList::new(6);
+        g.add_edge(0, 1, 7);
+        g.add_edge(0, 2, 9);
+        g.add_edge(0, 5, 14);
+        g.add_edge(1, 2, 10);
+        g.add_edge(1, 3, 15);
+        g.add_edge(2, 3, 11);
+        g.add_edge(2, 5, 2);
+        g.add_edge(3, 4, 6);
+        g.add_edge(5, 4, 9);
+        g
+    }
+
+    #[test]
+    fn bfs_counts_edges() {
+        let path = bfs(&sample(), 0, |n| *n == 4).unwrap();
+        assert_eq!(
+            path,
+            Path {
+                nodes: vec![0, 5, 4],
+                cost: 2
+            }
+        );
+        assert_eq!(bfs(&sample(), 4, |n| *n == 0), None);
+        assert_eq!(bfs(&sample(), 3, |n| *n == 3).unwrap().nodes, [3]);
+    }
+
+    #[test]
+    fn astar_reopens_nodes_for_inconsistent_heuristics() {
+        // 0 -> 2 looks cheap, so 2 is explored before the cheaper way to it
+        // through 1, whose heuristic is high but still admissible.
+        let mut g = AdjacencyList::new(4);
+        g.add_edge(0, 2, 4);
+        g.add_edge(0, 1, 1);
+        g.add_edge(1, 2, 1);
+        g.add_edge(2, 3, 4);
+        let heuristic = |n: &usize| if *n == 1 { 5 } else { 0 };
+        let path = astar(&g, 0, |n| *n == 3, heuristic).unwrap();
+        assert_eq!(
+            path,
+            Path {
+                nodes: vec![0, 1, 2, 3],
+                cost: 6
+            }
+        );
+    }
+
+    #[test]
+    fn dijkstra_finds_cheapest() {
+        let path = dijkstra(&sample(), 0, |n| *n == 4).unwrap();
+        assert_eq!(
+            path,
+            Path {
+                nodes: vec![0, 2, 5, 4],
+                cost: 20
+            }
+        );
+    }
+
+    #[test]
+    fn astar_on_implicit_grid() {
+        // Walk a 10x10 grid around a wall at x = 5 that is open only at y = 9.
+        let grid = implicit(|&(x, y): &(i32, i32)| {
+            [(1, 0), (-1, 0), (0, 1), (0, -1)]
+                .iter()
+                .map(|(dx, dy)| (x + dx, y + dy))
+                .filter(|(x, y)| (0..10).contains(x) && (0..10).contains(y))
+                .filter(|(x, y)| *x != 5 || *y == 9)
+                .map(|p| (p, 1))
+                .collect()
+        });
+        let goal = (9, 0);
+        let manhattan = |&(x, y): &(i32, i32)| ((goal.0 - x).abs() + (goal.1 - y).abs()) as u64;
+        let path = astar(&grid, (0, 0), |p| *p == goal, manhattan).unwrap();
+        assert_eq!(path.cost, 9 + 9 + 9);
+        assert_eq!(path.nodes.len() as u64, path.cost + 1);
+        assert!(path.nodes.contains(&(5, 9)));
+        assert_eq!(
+            dijkstra(&grid, (0, 0), |p| *p == goal).unwrap().cost,
+            path.cost
+        );
+    }
+
+    #[test]
+    fn dijkstra_matches_bellman_ford() {
+        let mut rng = Rng::new(21);
+        for _ in 0..100 {
+            let n = rng.range(1, 9) as usize;
+            let mut g = AdjacencyList::new(n);
+            let mut edges = vec![];
+            for _ in 0..rng.range(0, 20) {
+                let (a, b, w) = (
+                    rng.range(0, n as u64 - 1) as usize,
+                    rng.range(0, n as u64 - 1) as usize,
+                    rng.range(0, 10),
+                );
+                g.add_edge(a, b, w);
+                edges.push((a, b, w));
+            }
+            let mut best = vec![None; n];
+            best[0] = Some(0);
+            for _ in 0..n {
+                for (a, b, w) in &edges {
+                    if let Some(c) = best[*a]
+                        && best[*b].is_none_or(|old| c + w < old)
+                    {
+                        best[*b] = Some(c + w);
+                    }
+                }
+            }
+            for (target, expected) in best.iter().enumerate() {
+                let path = dijkstra(&g, 0, |v| *v == target);
+                assert_eq!(path.as_ref().map(|p| p.cost), *expected);
+                if let Some(p) = path {
+                    let walked: u64 = p
+                        .nodes
+                        .windows(2)
+                        .map(|w| {
+                            edges
+                                .iter()
+                                .filter(|e| (e.0, e.1) == (w[0], w[1]))
+                                .map(|e| e.2)
+                                .min()
+                                .unwrap()
+                        })
+                        .sum();
+                    assert_eq!(walked, p.cost);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn components_ignore_direction() {
+        let mut g = AdjacencyList::new(6);
+        g.add_edge(3, 0, 1);
+        g.add_edge(4, 3, 1);
+        g.add_undirected(1, 5, 1);
+        assert_eq!(
+            connected_components(&g),
+            vec![vec![0, 3, 4], vec![1, 5], vec![2]]
+        );
+    }
+
+    #[test]
+    fn topological_sort_orders_or_reports_cycle() {
+        assert_eq!(topological_sort(&sample()), Ok(vec![0, 1, 2, 3, 5, 4]));
+        let mut cyclic = sample();
+        cyclic.add_edge(4, 1, 1);
+        assert_eq!(topological_sort(&cyclic), Err(vec![1, 2, 3, 4, 5]));
+    }
+}
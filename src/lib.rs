@@ -1,10 +1,109 @@
+pub mod compress;
+pub mod day1;
+pub mod day10;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod diff;
+pub mod generate;
+pub mod geometry;
+pub mod graph;
+pub mod num;
+pub mod parse;
+pub mod polygon;
+pub mod readme;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod watch;
+
 pub mod util {
-    pub fn parse_args(args: &[String]) -> Result<&str, &'static str> {
+    use std::fmt::Display;
+
+    use crate::runner::Format;
+
+    /// Prints a line a `dayN` binary adds after the answers, such as an export
+    /// notice or a report. It goes to stderr with `--format json` so stdout
+    /// stays a single JSON document.
+    pub fn print_extra(format: Format, line: impl Display) {
+        match format {
+            Format::Text => println!("{line}"),
+            Format::Json => eprintln!("{line}"),
+        }
+    }
+
+    /// The input path and output format for a `dayN` binary. `--format` is
+    /// taken out of `args` so the remaining options start at `args[2]`.
+    pub fn parse_args(args: &mut Vec<String>) -> Result<(String, Format), &'static str> {
+        let format = Format::take(args)?;
         if args.len() < 2 {
             return Err("Not enough arguments.");
         }
-        let file_path = &args[1];
-        println!("Loading: {file_path}");
-        Ok(file_path)
+        Ok((args[1].clone(), format))
+    }
+
+    /// Small seeded xorshift64* generator so tests and tools can produce
+    /// repeatable random inputs without pulling in a dependency.
+    #[derive(Debug, Clone)]
+    pub struct Rng {
+        state: u64,
+    }
+
+    impl Rng {
+        pub fn new(seed: u64) -> Rng {
+            // Zero is a fixed point of xorshift, so the one seed that mixes
+            // to it gets a fixed non-zero state instead.
+            let state = match seed ^ 0x9E37_79B9_7F4A_7C15 {
+                0 => 0x2545_F491_4F6C_DD1D,
+                mixed => mixed,
+            };
+            Rng { state }
+        }
+
+        pub fn next_u64(&mut self) -> u64 {
+            self.state ^= self.state >> 12;
+            self.state ^= self.state << 25;
+            self.state ^= self.state >> 27;
+            self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+        }
+
+        /// Uniform-enough value in `low..=high`.
+        pub fn range(&mut self, low: u64, high: u64) -> u64 {
+            match (high - low).checked_add(1) {
+                Some(span) => low + self.next_u64() % span,
+                None => self.next_u64(),
+            }
+        }
+
+        pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+            self.next_u64() % denominator < numerator
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn every_seed_gives_a_moving_stream() {
+            for seed in [0, 1, 0x9E37_79B9_7F4A_7C15] {
+                let mut rng = Rng::new(seed);
+                let draws: Vec<u64> = (0..4).map(|_| rng.next_u64()).collect();
+                assert!(draws.windows(2).all(|w| w[0] != w[1]), "{seed}: {draws:?}");
+            }
+        }
+
+        #[test]
+        fn range_covers_the_full_width() {
+            let mut rng = Rng::new(7);
+            let _ = rng.range(0, u64::MAX);
+            assert_eq!(rng.range(u64::MAX, u64::MAX), u64::MAX);
+            assert!((3..=5).contains(&rng.range(3, 5)));
+        }
     }
 }
@@ -1,3 +1,366 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+use advent_of_code::diff;
+use advent_of_code::generate::{self, Knobs};
+use advent_of_code::readme;
+use advent_of_code::runner::{self, Format};
+use advent_of_code::scaffold;
+use advent_of_code::solution::{self, Answers, Day};
+use advent_of_code::watch::{self as watcher, Watcher};
+
+const USAGE: &str = "Usage: advent_of_code <day> [input] [--format text|json]
+       advent_of_code all [--format text|json]
+       advent_of_code new <day> [title]
+       advent_of_code extract <day> [--block <n>] [--force] [--list]
+       advent_of_code generate <day> [--size <n>] [--width <n>] [--seed <n>]
+       advent_of_code watch <day> [input] [--interval <ms>] [--reexec]
+           (edits under src/ are only picked up with --reexec)
+       advent_of_code diff <day> [input] [--generated <count>] [--size <n>] [--seed <n>]";
+
 fn main() {
-    println!("Hello, world!");
+    let mut args: Vec<String> = env::args().collect();
+    let format = Format::take(&mut args).unwrap_or_else(|err| {
+        println!("{err}\n{USAGE}");
+        process::exit(1);
+    });
+    if args.len() < 2 {
+        println!("{USAGE}");
+        process::exit(1);
+    }
+    match args[1].as_str() {
+        "all" => {
+            let (runs, wall_clock) = runner::run_all(solution::DAYS);
+            match format {
+                Format::Text => print!("{}", runner::summary_table(&runs, wall_clock)),
+                Format::Json => print!("{}", runner::to_json(&runs, wall_clock)),
+            }
+        }
+        "new" => new_day(&args[2..]),
+        "extract" => extract(&args[2..]),
+        "generate" => generate(&args[2..]),
+        "watch" => watch(&args[2..]),
+        "diff" => diff(&args[2..]),
+        _ => {
+            let day = args[1]
+                .parse::<u32>()
+                .ok()
+                .and_then(solution::find)
+                .unwrap_or_else(|| {
+                    println!("Unknown day: {}\n{USAGE}", args[1]);
+                    process::exit(1);
+                });
+            run_day(day, args.get(2).cloned(), format)
+        }
+    }
+}
+
+fn parse_day(arg: Option<&String>) -> u32 {
+    arg.and_then(|d| d.parse::<u32>().ok()).unwrap_or_else(|| {
+        println!("Expected a day number.\n{USAGE}");
+        process::exit(1);
+    })
+}
+
+fn new_day(args: &[String]) {
+    let day = parse_day(args.first());
+    let title = args.get(1).map(|t| t.as_str()).unwrap_or("TODO");
+    match scaffold::new_day(Path::new("."), day, title) {
+        Ok(written) => {
+            for path in written {
+                println!("Wrote {}", path.display());
+            }
+        }
+        Err(e) => {
+            println!("Could not create day {day}: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn extract(args: &[String]) {
+    let day = parse_day(args.first());
+    let mut block = 0;
+    let mut force = false;
+    let mut list = false;
+    let mut rest = args[1..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--block" => {
+                block = rest.next().and_then(|b| b.parse().ok()).unwrap_or_else(|| {
+                    println!("--block expects a block number.");
+                    process::exit(1);
+                })
+            }
+            "--force" => force = true,
+            "--list" => list = true,
+            _ => {
+                println!("Unknown option: {arg}\n{USAGE}");
+                process::exit(1);
+            }
+        }
+    }
+
+    if list {
+        let path = format!("challenges/day{day}/readme.md");
+        let raw = fs::read_to_string(&path).unwrap_or_else(|err| {
+            println!("Problem reading {path}: {err}");
+            process::exit(1);
+        });
+        for (i, example) in readme::example_blocks(&raw).iter().enumerate() {
+            println!("--- Block {i} ---\n{example}");
+        }
+        let expected = readme::expected_answers(&raw);
+        print!("--- Expected ---\n{}", expected.to_file());
+        return;
+    }
+    match readme::extract(Path::new("."), day, block, force) {
+        Ok(written) => {
+            for path in written {
+                println!("Wrote {}", path.display());
+            }
+        }
+        Err(e) => {
+            println!("Could not extract day {day}: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn generate(args: &[String]) {
+    let day = parse_day(args.first());
+    let mut knobs = Knobs::default();
+    let mut rest = args[1..].iter();
+    while let Some(arg) = rest.next() {
+        let value = rest.next().and_then(|v| v.parse::<u64>().ok());
+        match (arg.as_str(), value) {
+            ("--size", Some(v)) => knobs.size = v as usize,
+            ("--width", Some(v)) => knobs.width = v as usize,
+            ("--seed", Some(v)) => knobs.seed = v,
+            _ => {
+                println!("Unknown or incomplete option: {arg}\n{USAGE}");
+                process::exit(1);
+            }
+        }
+    }
+    match generate::generate(day, knobs) {
+        Ok(input) => print!("{input}"),
+        Err(e) => {
+            println!("Could not generate day {day}: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn diff(args: &[String]) {
+    let day = parse_day(args.first());
+    let mut input = None;
+    let mut generated = None;
+    let mut knobs = Knobs::default();
+    let mut rest = args[1..].iter();
+    while let Some(arg) = rest.next() {
+        if input.is_none() && !arg.starts_with("--") {
+            input = Some(arg.clone());
+            continue;
+        }
+        let value = rest.next().and_then(|v| v.parse::<u64>().ok());
+        match (arg.as_str(), value) {
+            ("--generated", Some(v)) => generated = Some(v),
+            ("--size", Some(v)) => knobs.size = v as usize,
+            ("--seed", Some(v)) => knobs.seed = v,
+            _ => {
+                println!("Unknown or incomplete option: {arg}\n{USAGE}");
+                process::exit(1);
+            }
+        }
+    }
+    if solution::IMPLEMENTATIONS.iter().all(|i| i.day != day) {
+        println!("Day {day} has no alternative implementations to compare.");
+        process::exit(1);
+    }
+
+    let Some(count) = generated else {
+        let path = input.unwrap_or_else(|| format!("challenges/day{day}/input.txt"));
+        let raw = fs::read_to_string(&path).unwrap_or_else(|err| {
+            println!("Problem reading {path}: {err}");
+            process::exit(1);
+        });
+        let comparisons = diff::compare(day, &raw);
+        print!("{}", diff::report(&comparisons));
+        if !comparisons.iter().all(|c| c.agrees()) {
+            process::exit(1);
+        }
+        return;
+    };
+
+    // Only inputs where the implementations disagree are shown in full.
+    let mut mismatches = 0;
+    for seed in (0..count).map(|i| knobs.seed.wrapping_add(i)) {
+        let raw = generate::generate(day, Knobs { seed, ..knobs }).unwrap_or_else(|e| {
+            println!("Could not generate day {day}: {e}");
+            process::exit(1);
+        });
+        let disagreeing: Vec<_> = diff::compare(day, &raw)
+            .into_iter()
+            .filter(|c| !c.agrees())
+            .collect();
+        if !disagreeing.is_empty() {
+            mismatches += 1;
+            print!("--- Seed {seed} ---\n{}", diff::report(&disagreeing));
+        }
+    }
+    println!("Checked {count} generated inputs, {mismatches} with mismatches.");
+    if mismatches > 0 {
+        process::exit(1);
+    }
+}
+
+fn run_day(day: &'static Day, file_path: Option<String>, format: Format) {
+    let file_path = file_path.unwrap_or_else(|| day.input_path());
+    if !runner::print_day(day, &file_path, format, day.run) {
+        process::exit(1);
+    }
+}
+
+fn watch(args: &[String]) {
+    let day = solution::find(parse_day(args.first())).unwrap_or_else(|| {
+        println!("Unknown day: {}\n{USAGE}", args[0]);
+        process::exit(1);
+    });
+    let mut input = None;
+    let mut interval = 500;
+    let mut reexec = false;
+    let mut rest = args[1..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--interval" => {
+                interval = rest.next().and_then(|i| i.parse().ok()).unwrap_or_else(|| {
+                    println!("--interval expects a number of milliseconds.");
+                    process::exit(1);
+                })
+            }
+            "--reexec" => reexec = true,
+            path if input.is_none() && !path.starts_with("--") => input = Some(path.to_string()),
+            _ => {
+                println!("Unknown option: {arg}\n{USAGE}");
+                process::exit(1);
+            }
+        }
+    }
+    let input = input.unwrap_or_else(|| day.input_path());
+    // Solvers lean on the shared modules, so any edit under src/ counts.
+    let source = PathBuf::from("src");
+
+    let mut files = Watcher::new(vec![PathBuf::from(&input), source.clone()]);
+    println!(
+        "Watching {input} and src/ for day {}. Press Ctrl-C to stop.",
+        day.day
+    );
+    // A re-exec hands over the answers the old process last saw.
+    let handed_over = env::var(watcher::PREVIOUS_ENV)
+        .ok()
+        .and_then(|raw| watcher::decode(&raw));
+    let mut previous = watch_run(day, &input, handed_over.as_ref()).or(handed_over);
+    loop {
+        thread::sleep(Duration::from_millis(interval));
+        let changed = files.changed();
+        if changed.is_empty() {
+            continue;
+        }
+        if changed.contains(&source) {
+            if reexec {
+                rebuild_and_reexec(previous.as_ref());
+            } else {
+                println!("Source changed, restart or pass --reexec to use the new code.");
+            }
+        }
+        previous = watch_run(day, &input, previous.as_ref()).or(previous);
+    }
+}
+
+// Runs the day once and prints its answers against the previous run's.
+fn watch_run(day: &'static Day, input: &str, previous: Option<&Answers>) -> Option<Answers> {
+    let run = runner::run_day(day, input);
+    println!("--- Day {}: {} ---", day.day, day.title);
+    match run.result {
+        Ok(answers) => {
+            for line in watcher::diff(previous, &answers) {
+                println!("{line}");
+            }
+            println!(
+                "Total: {}",
+                runner::format_duration(answers.timings.total())
+            );
+            Some(answers)
+        }
+        Err(e) => {
+            println!("Error: {e}");
+            None
+        }
+    }
+}
+
+// Cargo flags that build the same profile as the running binary, read from
+// the target directory it sits in: `target/debug` is the default profile,
+// anything else names its profile (`release` or a custom one).
+fn profile_args(exe: &Path) -> Vec<String> {
+    let dir = exe
+        .parent()
+        .and_then(Path::file_name)
+        .and_then(|d| d.to_str());
+    match dir {
+        Some("debug") => vec![],
+        Some("release") => vec!["--release".to_string()],
+        Some(profile) if exe.ancestors().any(|a| a.ends_with("target")) => {
+            vec!["--profile".to_string(), profile.to_string()]
+        }
+        // Not in a target directory, fall back to how this binary was built.
+        _ if cfg!(debug_assertions) => vec![],
+        _ => vec!["--release".to_string()],
+    }
+}
+
+// The running binary cannot pick up new solver code, so build it and replace
+// this process with the new binary, passing the same arguments and the last
+// answers. A failed build keeps the current process watching so the next save
+// can fix it.
+fn rebuild_and_reexec(previous: Option<&Answers>) {
+    // Look the binary up before cargo replaces it, after which the running
+    // process's own path points at the deleted file.
+    let exe = env::current_exe().unwrap_or_else(|err| {
+        println!("Could not find the current binary: {err}");
+        process::exit(1);
+    });
+    println!("Source changed, rebuilding...");
+    let built = process::Command::new("cargo")
+        .args(["build", "--quiet"])
+        .args(profile_args(&exe))
+        .status()
+        .is_ok_and(|s| s.success());
+    if !built {
+        println!("Build failed, still watching.");
+        return;
+    }
+    let mut command = process::Command::new(exe);
+    command.args(env::args().skip(1));
+    if let Some(answers) = previous {
+        command.env(watcher::PREVIOUS_ENV, watcher::encode(answers));
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        let err = command.exec();
+        println!("Could not restart: {err}");
+        process::exit(1);
+    }
+    // Without exec, run the new binary in the foreground and exit with it.
+    #[cfg(not(unix))]
+    {
+        let status = command.status();
+        process::exit(status.map_or(1, |s| s.code().unwrap_or(1)));
+    }
 }
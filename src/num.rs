@@ -0,0 +1,263 @@
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+/// The primitive integer types, signed and unsigned, so the helpers below
+/// work on whatever a day happens to store its numbers in.
+pub trait Integer:
+    Copy
+    + Ord
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const TEN: Self;
+
+    /// The unsigned type of the same width, which can hold every magnitude.
+    type Unsigned: Integer;
+
+    fn abs_value(self) -> Self;
+    /// The magnitude of `self`, which unlike `abs_value` cannot overflow.
+    fn unsigned_abs(self) -> Self::Unsigned;
+    fn euclid_div(self, rhs: Self) -> Self;
+    fn euclid_rem(self, rhs: Self) -> Self;
+    fn from_digit(digit: u8) -> Self;
+    /// The value of a single digit, `self` must be in `0..=9`.
+    fn to_digit(self) -> u8;
+}
+
+macro_rules! integer {
+    ($abs:expr, $unsigned_abs:expr => $($t:ty: $u:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: $t = 0;
+            const ONE: $t = 1;
+            const TEN: $t = 10;
+
+            type Unsigned = $u;
+
+            fn abs_value(self) -> $t {
+                $abs(self)
+            }
+            fn unsigned_abs(self) -> $u {
+                $unsigned_abs(self)
+            }
+            fn euclid_div(self, rhs: $t) -> $t {
+                self.div_euclid(rhs)
+            }
+            fn euclid_rem(self, rhs: $t) -> $t {
+                self.rem_euclid(rhs)
+            }
+            fn from_digit(digit: u8) -> $t {
+                digit as $t
+            }
+            fn to_digit(self) -> u8 {
+                self as u8
+            }
+        }
+    )*};
+}
+
+integer!(|n| n, |n| n => u8: u8, u16: u16, u32: u32, u64: u64, u128: u128, usize: usize);
+integer!(
+    |n: Self| n.abs(),
+    |n: Self| n.unsigned_abs() =>
+    i8: u8, i16: u16, i32: u32, i64: u64, i128: u128, isize: usize
+);
+
+/// Greatest common divisor, always non-negative. `gcd(0, 0)` is 0.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a.abs_value(), b.abs_value());
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, always non-negative. 0 if either side is.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        return T::ZERO;
+    }
+    (a / gcd(a, b) * b).abs_value()
+}
+
+/// Every positive divisor of `n`, smallest first, including 1 and `n`.
+pub fn divisors<T: Integer>(n: T) -> Vec<T> {
+    let n = n.abs_value();
+    let mut small = vec![];
+    let mut large = vec![];
+    let mut d = T::ONE;
+    while d <= n / d {
+        if n % d == T::ZERO {
+            small.push(d);
+            if d != n / d {
+                large.push(n / d);
+            }
+        }
+        d = d + T::ONE;
+    }
+    small.extend(large.into_iter().rev());
+    small
+}
+
+/// Euclidean quotient and remainder, so the remainder is never negative:
+/// `div_mod(-7, 3)` is `(-3, 2)`.
+pub fn div_mod<T: Integer>(a: T, b: T) -> (T, T) {
+    (a.euclid_div(b), a.euclid_rem(b))
+}
+
+/// The decimal digits of `n`, most significant first. The sign is ignored.
+pub fn digits<T: Integer>(n: T) -> Vec<u8> {
+    let mut n = n.unsigned_abs();
+    let ten = T::Unsigned::TEN;
+    let mut digits = vec![(n % ten).to_digit()];
+    n = n / ten;
+    while n != T::Unsigned::ZERO {
+        digits.push((n % ten).to_digit());
+        n = n / ten;
+    }
+    digits.reverse();
+    digits
+}
+
+/// Builds a number from decimal digits, most significant first.
+pub fn from_digits<T: Integer>(digits: impl IntoIterator<Item = u8>) -> T {
+    digits
+        .into_iter()
+        .fold(T::ZERO, |n, d| n * T::TEN + T::from_digit(d))
+}
+
+/// How many decimal digits `n` has. 0 has one digit.
+pub fn digit_count<T: Integer>(n: T) -> u32 {
+    let ten = T::Unsigned::TEN;
+    let mut n = n.unsigned_abs() / ten;
+    let mut count = 1;
+    while n != T::Unsigned::ZERO {
+        n = n / ten;
+        count += 1;
+    }
+    count
+}
+
+/// `base` raised to `exp`.
+pub fn pow<T: Integer>(base: T, exp: u32) -> T {
+    (0..exp).fold(T::ONE, |n, _| n * base)
+}
+
+/// `len` ones written in `base`: `repunit(3, 10)` is 111 and
+/// `repunit(2, 1000)` is 1001. Multiplying a block of digits by
+/// `repunit(k, 10^block_len)` repeats it `k` times.
+pub fn repunit<T: Integer>(len: u32, base: T) -> T {
+    (0..len).fold(T::ZERO, |n, _| n * base + T::ONE)
+}
+
+/// `base^exp mod modulus` by repeated squaring. Products are built up by
+/// doubling so nothing overflows while `modulus` is below half of `T::MAX`.
+pub fn modpow<T: Integer>(base: T, exp: T, modulus: T) -> T {
+    let two = T::ONE + T::ONE;
+    let mut result = T::ONE.euclid_rem(modulus);
+    let mut base = base.euclid_rem(modulus);
+    let mut exp = exp;
+    while exp > T::ZERO {
+        if exp % two == T::ONE {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exp = exp / two;
+    }
+    result
+}
+
+fn mul_mod<T: Integer>(a: T, b: T, modulus: T) -> T {
+    let two = T::ONE + T::ONE;
+    let (mut a, mut b) = (a, b);
+    let mut result = T::ZERO;
+    while b > T::ZERO {
+        if b % two == T::ONE {
+            result = (result + a) % modulus;
+        }
+        a = (a + a) % modulus;
+        b = b / two;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::Rng;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12i32, 18), 6);
+        assert_eq!(gcd(0u8, 0), 0);
+        assert_eq!(lcm(4u64, 6), 12);
+        assert_eq!(lcm(-4i64, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+    }
+
+    #[test]
+    fn divisors_in_order() {
+        assert_eq!(divisors(12), vec![1, 2, 3, 4, 6, 12]);
+        assert_eq!(divisors(49u32), vec![1, 7, 49]);
+        assert_eq!(divisors(1usize), vec![1]);
+        assert_eq!(divisors(255u8), vec![1, 3, 5, 15, 17, 51, 85, 255]);
+    }
+
+    #[test]
+    fn div_mod_is_euclidean() {
+        assert_eq!(div_mod(-7, 3), (-3, 2));
+        assert_eq!(div_mod(7, -3), (-2, 1));
+        assert_eq!(div_mod(-250, 100), (-3, 50));
+        assert_eq!(div_mod(7u32, 3), (2, 1));
+    }
+
+    #[test]
+    fn digits_round_trip() {
+        assert_eq!(digits(1203), vec![1, 2, 0, 3]);
+        assert_eq!(digits(0u8), vec![0]);
+        assert_eq!(digits(-45i64), vec![4, 5]);
+        assert_eq!(from_digits::<u64>([9, 8, 0]), 980);
+        assert_eq!(digit_count(0), 1);
+        assert_eq!(digit_count(99), 2);
+        assert_eq!(digit_count(u64::MAX), 20);
+        assert_eq!(digits(i64::MIN), digits(i64::MIN.unsigned_abs()));
+        assert_eq!(digit_count(i8::MIN), 3);
+        let mut rng = Rng::new(4);
+        for _ in 0..500 {
+            let n = rng.next_u64() >> rng.range(0, 63);
+            assert_eq!(from_digits::<u64>(digits(n)), n);
+            assert_eq!(digit_count(n) as usize, n.to_string().len());
+        }
+    }
+
+    #[test]
+    fn repunits() {
+        assert_eq!(repunit(3, 10), 111);
+        assert_eq!(repunit(2, 1000u64), 1001);
+        assert_eq!(repunit(0, 10), 0);
+        assert_eq!(12 * repunit(3, 100), 121212);
+        assert_eq!(pow(10u64, 0), 1);
+        assert_eq!(pow(3i32, 4), 81);
+    }
+
+    #[test]
+    fn modpow_matches_repeated_multiplication() {
+        assert_eq!(modpow(2u64, 10, 1000), 24);
+        assert_eq!(modpow(-2i64, 3, 5), 2);
+        assert_eq!(modpow(5u8, 0, 1), 0);
+        let big = (1u64 << 61) - 1;
+        assert_eq!(modpow(3, big - 1, big), 1);
+        let mut rng = Rng::new(8);
+        for _ in 0..200 {
+            let (b, e, m) = (rng.range(0, 1000), rng.range(0, 30), rng.range(1, 1000));
+            let slow = (0..e).fold(1 % m, |acc, _| acc * b % m);
+            assert_eq!(modpow(b, e, m), slow, "{b}^{e} mod {m}");
+        }
+    }
+}
@@ -0,0 +1,362 @@
+use std::error::Error;
+use std::fmt;
+use std::num::{IntErrorKind, ParseIntError};
+use std::str::FromStr;
+
+/// A 1-based line and column (counted in chars) within an input.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A parsing failure, pointing at where in the input it happened when that
+/// is known.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParseError {
+    pub location: Option<Location>,
+    pub message: String,
+}
+
+impl ParseError {
+    /// An error about the input as a whole.
+    pub fn new(message: impl Into<String>) -> ParseError {
+        ParseError {
+            location: None,
+            message: message.into(),
+        }
+    }
+
+    /// An error at the start of `part`, which must be a slice of `raw`.
+    pub fn at(raw: &str, part: &str, message: impl Into<String>) -> ParseError {
+        ParseError {
+            location: Some(locate(raw, part)),
+            message: message.into(),
+        }
+    }
+
+    /// Moves an error reported against `part` so it is relative to `raw`,
+    /// for helpers that were handed one section of a larger input.
+    pub fn within(self, raw: &str, part: &str) -> ParseError {
+        let start = locate(raw, part);
+        let location = match self.location {
+            Some(l) if l.line == 1 => Location {
+                line: start.line,
+                column: start.column + l.column - 1,
+            },
+            Some(l) => Location {
+                line: start.line + l.line - 1,
+                column: l.column,
+            },
+            None => start,
+        };
+        ParseError {
+            location: Some(location),
+            message: self.message,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.location {
+            Some(l) => write!(f, "line {}, column {}: {}", l.line, l.column, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<&'static str> for ParseError {
+    fn from(message: &'static str) -> ParseError {
+        ParseError::new(message)
+    }
+}
+
+/// Where `part` starts within `raw`. `part` must be a slice of `raw`;
+/// anything else is reported as the very start.
+pub fn locate(raw: &str, part: &str) -> Location {
+    let offset = (part.as_ptr() as usize).wrapping_sub(raw.as_ptr() as usize);
+    let before = raw.get(..offset).unwrap_or("");
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Location {
+        line: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count() + 1,
+    }
+}
+
+/// Every integer in `raw`, in order, ignoring whatever separates them. A `-`
+/// is a sign only when it does not follow a digit, so `3-5` is two positive
+/// numbers while `x=-5` is negative.
+pub fn ints<T: FromStr<Err = ParseIntError>>(raw: &str) -> Result<Vec<T>, ParseError> {
+    let bytes = raw.as_bytes();
+    let mut numbers = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        let signed = bytes[i] == b'-'
+            && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+            && (i == 0 || !bytes[i - 1].is_ascii_digit());
+        if !signed && !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        let text = &raw[start..i];
+        let n = text.parse().map_err(|e: ParseIntError| {
+            let message = match e.kind() {
+                IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => "is out of range",
+                _ => "is not a number",
+            };
+            ParseError::at(raw, text, format!("{text} {message}"))
+        })?;
+        numbers.push(n);
+    }
+    Ok(numbers)
+}
+
+/// Splits `raw` on `separator` and builds an item from each piece. Pieces
+/// are trimmed and empty ones skipped, so a trailing separator or newline is
+/// fine. Errors point at the piece that failed.
+pub fn delimited<T, E: fmt::Display>(
+    raw: &str,
+    separator: char,
+    mut build: impl FnMut(&str) -> Result<T, E>,
+) -> Result<Vec<T>, ParseError> {
+    raw.split(separator)
+        .map(str::trim)
+        .filter(|piece| !piece.is_empty())
+        .map(|piece| build(piece).map_err(|e| ParseError::at(raw, piece, e.to_string())))
+        .collect()
+}
+
+/// Builds an item from every non-blank line. Errors point at the line that
+/// failed.
+pub fn lines<T, E: fmt::Display>(
+    raw: &str,
+    mut build: impl FnMut(&str) -> Result<T, E>,
+) -> Result<Vec<T>, ParseError> {
+    raw.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| build(line).map_err(|e| ParseError::at(raw, line, e.to_string())))
+        .collect()
+}
+
+/// Pairs like `a=1, b=2`, split on `separator` between pairs and `equals`
+/// within them. Keys and values are trimmed.
+pub fn key_values(
+    raw: &str,
+    separator: char,
+    equals: char,
+) -> Result<Vec<(&str, &str)>, ParseError> {
+    raw.split(separator)
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            pair.split_once(equals)
+                .map(|(k, v)| (k.trim(), v.trim()))
+                .ok_or_else(|| ParseError::at(raw, pair, format!("expected a '{equals}'")))
+        })
+        .collect()
+}
+
+/// The blocks of `raw` separated by one or more blank lines. Each block is a
+/// slice of `raw`, so errors found in it can be moved back with
+/// `ParseError::within`.
+pub fn sections(raw: &str) -> Vec<&str> {
+    let mut sections = vec![];
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+    for line in raw.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(s) = start.take() {
+                sections.push(&raw[s..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + line.trim_end_matches(['\n', '\r']).len();
+        }
+        offset += line.len();
+    }
+    if let Some(s) = start {
+        sections.push(&raw[s..end]);
+    }
+    sections
+}
+
+/// The `[start, end)` char columns of each block of text in `rows`, where
+/// blocks are separated by columns that are blank in every row. Short rows
+/// count as blank past their end.
+pub fn column_spans(rows: &[&str]) -> Vec<(usize, usize)> {
+    let chars: Vec<Vec<char>> = rows.iter().map(|r| r.chars().collect()).collect();
+    let width = chars.iter().map(Vec::len).max().unwrap_or(0);
+    let blank = |column: usize| {
+        chars
+            .iter()
+            .all(|r| r.get(column).is_none_or(|c| *c == ' '))
+    };
+
+    let mut spans = vec![];
+    let mut start = None;
+    for column in 0..=width {
+        match (column == width || blank(column), start) {
+            (false, None) => start = Some(column),
+            (true, Some(s)) => {
+                spans.push((s, column));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    spans
+}
+
+/// Cuts `line` into fields of the given char widths. The last field may be
+/// short or missing if the line is, which gives an empty field.
+pub fn fixed_columns<'a>(line: &'a str, widths: &[usize]) -> Vec<&'a str> {
+    let mut fields = vec![];
+    let mut rest = line;
+    for width in widths {
+        let cut = rest
+            .char_indices()
+            .nth(*width)
+            .map_or(rest.len(), |(i, _)| i);
+        let (field, tail) = rest.split_at(cut);
+        fields.push(field);
+        rest = tail;
+    }
+    fields
+}
+
+/// A rectangular grid built one char at a time. Errors point at the cell
+/// that failed or the row whose width differs from the first.
+pub fn grid<T, E: fmt::Display>(
+    raw: &str,
+    mut cell: impl FnMut(char) -> Result<T, E>,
+) -> Result<Vec<Vec<T>>, ParseError> {
+    let mut rows: Vec<Vec<T>> = vec![];
+    for line in raw.lines().filter(|l| !l.is_empty()) {
+        let mut row = vec![];
+        for (i, c) in line.char_indices() {
+            let here = &line[i..];
+            row.push(cell(c).map_err(|e| ParseError::at(raw, here, e.to_string()))?);
+        }
+        if let Some(first) = rows.first()
+            && first.len() != row.len()
+        {
+            return Err(ParseError::at(
+                raw,
+                line,
+                format!("row is {} wide, expected {}", row.len(), first.len()),
+            ));
+        }
+        rows.push(row);
+    }
+    if rows.is_empty() {
+        return Err(ParseError::new("empty grid"));
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(line: usize, column: usize) -> Option<Location> {
+        Some(Location { line, column })
+    }
+
+    #[test]
+    fn ints_handle_signs_and_separators() {
+        assert_eq!(ints::<i64>("3-5, x=-7 y=+2\n-1"), Ok(vec![3, 5, -7, 2, -1]));
+        assert_eq!(ints::<u8>("no numbers"), Ok(vec![]));
+        let err = ints::<u8>("1,\n 300").unwrap_err();
+        assert_eq!(err.location, at(2, 2));
+        assert_eq!(err.to_string(), "line 2, column 2: 300 is out of range");
+        let err = ints::<u8>("x=-3").unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 3: -3 is not a number");
+        let err = ints::<i8>("-300").unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 1: -300 is out of range");
+    }
+
+    #[test]
+    fn delimited_skips_empty_pieces_and_locates_errors() {
+        let parse = |raw| delimited(raw, ',', |p: &str| p.parse::<u32>());
+        assert_eq!(parse("1, 2,,3,\n"), Ok(vec![1, 2, 3]));
+        let err = parse("1,2, x").unwrap_err();
+        assert_eq!(err.location, at(1, 6));
+    }
+
+    #[test]
+    fn lines_skip_blanks() {
+        let err = lines("1\n\n2\nb\n", |l: &str| l.parse::<u32>()).unwrap_err();
+        assert_eq!(err.location, at(4, 1));
+        assert_eq!(
+            lines("1\n\n2\n", |l: &str| l.parse::<u32>()),
+            Ok(vec![1, 2])
+        );
+    }
+
+    #[test]
+    fn key_values_split_pairs() {
+        assert_eq!(
+            key_values("a=1, b = 2\n", ',', '='),
+            Ok(vec![("a", "1"), ("b", "2")])
+        );
+        assert_eq!(
+            key_values("a=1,b", ',', '=').unwrap_err().location,
+            at(1, 5)
+        );
+    }
+
+    #[test]
+    fn sections_split_on_blank_lines() {
+        let raw = "a\nb\n\n\nc\r\n  \nd";
+        assert_eq!(sections(raw), ["a\nb", "c", "d"]);
+        assert_eq!(sections("\n\n"), Vec::<&str>::new());
+        let err = lines(sections(raw)[1], |_| Err::<(), _>("bad"))
+            .unwrap_err()
+            .within(raw, sections(raw)[1]);
+        assert_eq!(err.location, at(5, 1));
+    }
+
+    #[test]
+    fn within_shifts_locations() {
+        let raw = "xx abc\ndef";
+        let part = &raw[3..];
+        let moved = |line, column| ParseError {
+            location: at(line, column),
+            message: String::new(),
+        };
+        assert_eq!(moved(1, 2).within(raw, part).location, at(1, 5));
+        assert_eq!(moved(2, 2).within(raw, part).location, at(2, 2));
+        assert_eq!(ParseError::new("").within(raw, part).location, at(1, 4));
+    }
+
+    #[test]
+    fn column_spans_find_blank_columns() {
+        let rows = ["123 328  51", " 45 64  387", "  6 98", "*   +    *"];
+        assert_eq!(column_spans(&rows), [(0, 3), (4, 7), (8, 11)]);
+        assert_eq!(column_spans(&[]), []);
+    }
+
+    #[test]
+    fn fixed_columns_cut_by_width() {
+        assert_eq!(fixed_columns("abcdef", &[2, 3, 4]), ["ab", "cde", "f"]);
+        assert_eq!(fixed_columns("ab", &[3, 1]), ["ab", ""]);
+    }
+
+    #[test]
+    fn grid_checks_cells_and_width() {
+        let cell = |c: char| c.to_digit(10).ok_or("not a digit");
+        assert_eq!(grid("12\n34\n", cell), Ok(vec![vec![1, 2], vec![3, 4]]));
+        assert_eq!(grid("12\n3x\n", cell).unwrap_err().location, at(2, 2));
+        assert_eq!(grid("12\n345\n", cell).unwrap_err().location, at(2, 1));
+        assert!(grid("", cell).unwrap_err().location.is_none());
+    }
+}
@@ -0,0 +1,320 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::geometry::{Point2, Rect};
+use crate::num::Integer;
+
+/// Why a list of vertices is not a simple closed rectilinear polygon. Edge
+/// `i` runs from vertex `i` to vertex `i + 1`, wrapping around at the end.
+#[derive(Debug, PartialEq, Clone)]
+pub enum PolygonError {
+    TooFewVertices(usize),
+    /// Edges that are diagonal or have no length.
+    NotRectilinear(Vec<usize>),
+    /// Pairs of edges that touch or cross somewhere other than the vertex
+    /// they share.
+    SelfIntersecting(Vec<(usize, usize)>),
+}
+
+impl fmt::Display for PolygonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PolygonError::TooFewVertices(n) => {
+                write!(f, "A polygon needs at least 4 vertices, got {n}")
+            }
+            PolygonError::NotRectilinear(edges) => {
+                write!(f, "Edges are not horizontal or vertical: {edges:?}")
+            }
+            PolygonError::SelfIntersecting(pairs) => {
+                write!(f, "Edges cross each other: {pairs:?}")
+            }
+        }
+    }
+}
+
+impl Error for PolygonError {}
+
+/// Every edge of the closed polygon, the last one joining back to the first
+/// vertex.
+pub fn edges<T: Integer>(vertices: &[Point2<T>]) -> impl Iterator<Item = (Point2<T>, Point2<T>)> {
+    (0..vertices.len()).map(|i| (vertices[i], vertices[(i + 1) % vertices.len()]))
+}
+
+/// Checks the vertices form a simple closed polygon whose edges are all
+/// horizontal or vertical, which everything else in this module assumes.
+pub fn validate<T: Integer>(vertices: &[Point2<T>]) -> Result<(), PolygonError> {
+    if vertices.len() < 4 {
+        return Err(PolygonError::TooFewVertices(vertices.len()));
+    }
+    let all: Vec<(Point2<T>, Point2<T>)> = edges(vertices).collect();
+    let diagonal: Vec<usize> = (0..all.len())
+        .filter(|i| {
+            let (a, b) = all[*i];
+            (a.x == b.x) == (a.y == b.y)
+        })
+        .collect();
+    if !diagonal.is_empty() {
+        return Err(PolygonError::NotRectilinear(diagonal));
+    }
+
+    let n = all.len();
+    let mut crossings = vec![];
+    for i in 0..n {
+        for j in i + 1..n {
+            let (a, b) = (
+                Rect::from_corners(all[i].0, all[i].1),
+                Rect::from_corners(all[j].0, all[j].1),
+            );
+            let Some(shared) = a.intersection(&b) else {
+                continue;
+            };
+            // Neighbouring edges may only meet at their common vertex.
+            let neighbours = j == i + 1 || (i == 0 && j == n - 1);
+            let common = if j == i + 1 { all[i].1 } else { all[i].0 };
+            if !neighbours || shared != Rect::from_corners(common, common) {
+                crossings.push((i, j));
+            }
+        }
+    }
+    if !crossings.is_empty() {
+        return Err(PolygonError::SelfIntersecting(crossings));
+    }
+    Ok(())
+}
+
+/// Area enclosed by the polygon through the vertices, by the shoelace
+/// formula. Exact for rectilinear polygons, rounded down otherwise.
+pub fn shoelace_area<T: Integer>(vertices: &[Point2<T>]) -> T {
+    let (mut positive, mut negative) = (T::ZERO, T::ZERO);
+    for (a, b) in edges(vertices) {
+        positive = positive + a.x * b.y;
+        negative = negative + b.x * a.y;
+    }
+    let twice = if positive > negative {
+        positive - negative
+    } else {
+        negative - positive
+    };
+    twice / (T::ONE + T::ONE)
+}
+
+/// Whether `p` is inside the rectilinear polygon or on its boundary.
+pub fn contains<T: Integer>(vertices: &[Point2<T>], p: &Point2<T>) -> bool {
+    let mut inside = false;
+    for (a, b) in edges(vertices) {
+        if Rect::from_corners(a, b).contains(p) {
+            return true;
+        }
+        // Cast a ray towards +x and count the vertical edges it crosses,
+        // taking each edge as half open so a vertex is only counted once.
+        let (low, high) = (a.y.min(b.y), a.y.max(b.y));
+        if a.x == b.x && a.x > p.x && low <= p.y && p.y < high {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Whether any edge passes through the inside of `rect`, not counting its
+/// border.
+pub fn rect_crosses_edges<T: Integer>(vertices: &[Point2<T>], rect: &Rect<T>) -> bool {
+    edges(vertices).any(|(a, b)| {
+        let edge = Rect::from_corners(a, b);
+        let across_x = rect.min.x < edge.max.x && edge.min.x < rect.max.x;
+        let across_y = rect.min.y < edge.max.y && edge.min.y < rect.max.y;
+        let inside_x = rect.min.x < edge.min.x && edge.min.x < rect.max.x;
+        let inside_y = rect.min.y < edge.min.y && edge.min.y < rect.max.y;
+        (a.x == b.x && inside_x && across_y) || (a.y == b.y && inside_y && across_x)
+    })
+}
+
+/// Whether every tile of `rect`, that is every point with whole coordinates,
+/// lies inside the rectilinear polygon or on its boundary. A gap between two
+/// edges one unit apart holds no tiles, so it does not count as outside.
+pub fn contains_rect<T: Integer>(vertices: &[Point2<T>], rect: &Rect<T>) -> bool {
+    let xs = sample_points(vertices.iter().map(|v| v.x), rect.min.x, rect.max.x);
+    let ys = sample_points(vertices.iter().map(|v| v.y), rect.min.y, rect.max.y);
+    xs.iter()
+        .all(|x| ys.iter().all(|y| contains(vertices, &Point2::new(*x, *y))))
+}
+
+// The whole coordinates in `low..=high` where containment can change: both
+// ends, each vertex coordinate and the one after it. Every other coordinate
+// lies strictly between two vertex coordinates and behaves like the one
+// after the lower of them.
+fn sample_points<T: Integer>(vertex: impl Iterator<Item = T>, low: T, high: T) -> Vec<T> {
+    let mut points = vec![low, high];
+    for v in vertex {
+        points.extend(
+            [v, v + T::ONE]
+                .into_iter()
+                .filter(|p| low <= *p && *p <= high),
+        );
+    }
+    points.sort_unstable();
+    points.dedup();
+    points
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::util::Rng;
+
+    fn points(raw: &[(i64, i64)]) -> Vec<Point2<i64>> {
+        raw.iter().map(|(x, y)| Point2::new(*x, *y)).collect()
+    }
+
+    fn example() -> Vec<Point2<i64>> {
+        points(&[
+            (7, 1),
+            (11, 1),
+            (11, 7),
+            (9, 7),
+            (9, 5),
+            (2, 5),
+            (2, 3),
+            (7, 3),
+        ])
+    }
+
+    // A random simple loop on a small grid: a skyline along the top joined to
+    // a skyline along the bottom.
+    pub(crate) fn random_loop(rng: &mut Rng) -> Vec<Point2<i64>> {
+        let skyline = |rng: &mut Rng, low: u64, high: u64| {
+            let mut xs: Vec<i64> = (0..rng.range(0, 4))
+                .map(|_| rng.range(1, 9) as i64)
+                .collect();
+            xs.extend([0, 10]);
+            xs.sort_unstable();
+            xs.dedup();
+            let mut ys: Vec<i64> = vec![];
+            while ys.len() < xs.len() - 1 {
+                let y = rng.range(low, high) as i64;
+                if ys.last() != Some(&y) {
+                    ys.push(y);
+                }
+            }
+            (xs, ys)
+        };
+        let (top_x, top_y) = skyline(rng, 6, 10);
+        let (bottom_x, bottom_y) = skyline(rng, 0, 4);
+        let mut loop_ = vec![];
+        for (i, y) in top_y.iter().enumerate() {
+            loop_.extend([Point2::new(top_x[i], *y), Point2::new(top_x[i + 1], *y)]);
+        }
+        for (i, y) in bottom_y.iter().enumerate().rev() {
+            loop_.extend([
+                Point2::new(bottom_x[i + 1], *y),
+                Point2::new(bottom_x[i], *y),
+            ]);
+        }
+        loop_
+    }
+
+    #[test]
+    fn validate_reports_bad_edges() {
+        assert_eq!(validate(&example()), Ok(()));
+        assert_eq!(
+            validate(&points(&[(0, 0), (1, 0)])),
+            Err(PolygonError::TooFewVertices(2))
+        );
+        assert_eq!(
+            validate(&points(&[(0, 0), (4, 0), (4, 4), (1, 3)])),
+            Err(PolygonError::NotRectilinear(vec![2, 3]))
+        );
+        // A figure of eight crosses itself in the middle.
+        let eight = points(&[(0, 0), (2, 0), (2, 4), (4, 4), (4, 2), (0, 2)]);
+        assert_eq!(
+            validate(&eight),
+            Err(PolygonError::SelfIntersecting(vec![(1, 4)]))
+        );
+        // Doubling back along the previous edge overlaps it.
+        let back = points(&[(0, 0), (4, 0), (2, 0), (2, 3), (0, 3)]);
+        assert_eq!(
+            validate(&back),
+            Err(PolygonError::SelfIntersecting(vec![(0, 1), (0, 2)]))
+        );
+    }
+
+    #[test]
+    fn shoelace_area_of_example() {
+        // Three bands stacked on top of each other.
+        assert_eq!(shoelace_area(&example()), 8 + 18 + 4);
+        let mut reversed = example();
+        reversed.reverse();
+        assert_eq!(shoelace_area(&reversed), 30);
+    }
+
+    #[test]
+    fn contains_includes_boundary() {
+        let shape = example();
+        assert!(contains(&shape, &Point2::new(7, 1)));
+        assert!(contains(&shape, &Point2::new(9, 1)));
+        assert!(contains(&shape, &Point2::new(4, 4)));
+        assert!(contains(&shape, &Point2::new(10, 6)));
+        assert!(!contains(&shape, &Point2::new(4, 2)));
+        assert!(!contains(&shape, &Point2::new(12, 4)));
+        assert!(!contains(&shape, &Point2::new(4, 6)));
+    }
+
+    #[test]
+    fn rects_in_example() {
+        let shape = example();
+        let rect = |x1, y1, x2, y2| Rect::from_corners(Point2::new(x1, y1), Point2::new(x2, y2));
+        assert!(contains_rect(&shape, &rect(9, 5, 2, 3)));
+        assert!(contains_rect(&shape, &rect(7, 3, 11, 1)));
+        assert!(contains_rect(&shape, &rect(9, 7, 9, 5)));
+        assert!(!contains_rect(&shape, &rect(2, 5, 11, 1)));
+        assert!(!contains_rect(&shape, &rect(2, 3, 7, 1)));
+        assert!(rect_crosses_edges(&shape, &rect(2, 5, 11, 1)));
+        assert!(!rect_crosses_edges(&shape, &rect(2, 3, 7, 1)));
+    }
+
+    #[test]
+    fn contains_rect_counts_tiles() {
+        // Two edges one unit apart leave a gap with no tiles in it.
+        let comb = points(&[
+            (0, 0),
+            (5, 0),
+            (5, 3),
+            (6, 3),
+            (6, 0),
+            (10, 0),
+            (10, 5),
+            (0, 5),
+        ]);
+        let rect = |x1, y1, x2, y2| Rect::from_corners(Point2::new(x1, y1), Point2::new(x2, y2));
+        assert!(contains_rect(&comb, &rect(0, 0, 10, 5)));
+        let wide = points(&[
+            (0, 0),
+            (4, 0),
+            (4, 3),
+            (6, 3),
+            (6, 0),
+            (10, 0),
+            (10, 5),
+            (0, 5),
+        ]);
+        assert!(!contains_rect(&wide, &rect(0, 0, 10, 5)));
+        assert!(contains_rect(&wide, &rect(0, 3, 10, 5)));
+    }
+
+    #[test]
+    fn contains_rect_matches_tile_by_tile() {
+        let mut rng = Rng::new(12);
+        for _ in 0..200 {
+            let shape = random_loop(&mut rng);
+            assert_eq!(validate(&shape), Ok(()), "{shape:?}");
+            for _ in 0..20 {
+                let corner =
+                    |rng: &mut Rng| Point2::new(rng.range(0, 10) as i64, rng.range(0, 10) as i64);
+                let rect = Rect::from_corners(corner(&mut rng), corner(&mut rng));
+                let brute = (rect.min.x..=rect.max.x).all(|x| {
+                    (rect.min.y..=rect.max.y).all(|y| contains(&shape, &Point2::new(x, y)))
+                });
+                assert_eq!(contains_rect(&shape, &rect), brute, "{rect:?} in {shape:?}");
+            }
+        }
+    }
+}
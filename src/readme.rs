@@ -0,0 +1,237 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Answers a readme states for its examples, one per part.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Expected {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Expected {
+    /// Reads the `part1=..` / `part2=..` lines written by `extract`.
+    pub fn parse(raw: &str) -> Expected {
+        let mut expected = Expected::default();
+        for line in raw.lines() {
+            match line.split_once('=') {
+                Some(("part1", v)) => expected.part1 = Some(v.trim().to_string()),
+                Some(("part2", v)) => expected.part2 = Some(v.trim().to_string()),
+                _ => {}
+            }
+        }
+        expected
+    }
+
+    pub fn to_file(&self) -> String {
+        let mut out = String::new();
+        for (part, answer) in [(1, &self.part1), (2, &self.part2)] {
+            if let Some(a) = answer {
+                out.push_str(&format!("part{part}={a}\n"));
+            }
+        }
+        out
+    }
+}
+
+// Input lines never contain words, prose always does.
+fn is_data(line: &str) -> bool {
+    let mut run = 0;
+    for c in line.chars() {
+        run = if c.is_ascii_alphabetic() { run + 1 } else { 0 };
+        if run >= 3 {
+            return false;
+        }
+    }
+    !line.trim().is_empty()
+}
+
+/// Every run of input-like lines in the readme, in order. A single blank line
+/// between two input-like lines stays part of the block, as in day 5.
+pub fn example_blocks(readme: &str) -> Vec<String> {
+    let lines: Vec<&str> = readme.lines().collect();
+    let mut blocks = vec![];
+    let mut i = 0;
+    while i < lines.len() {
+        if !is_data(lines[i]) {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < lines.len()
+            && (is_data(lines[i])
+                || (lines[i].trim().is_empty()
+                    && i > start
+                    && lines.get(i + 1).is_some_and(|l| is_data(l))))
+        {
+            i += 1;
+        }
+        blocks.push(lines[start..i].join("\n") + "\n");
+    }
+    blocks
+}
+
+/// Finds the example answer for each part: the last number of the last
+/// sentence before the part's question that states a result.
+pub fn expected_answers(readme: &str) -> Expected {
+    let (first, second) = match readme.split_once("--- Part Two ---") {
+        Some((a, b)) => (a, Some(b)),
+        None => (readme, None),
+    };
+    Expected {
+        part1: stated_answer(first),
+        part2: second.and_then(stated_answer),
+    }
+}
+
+fn stated_answer(part: &str) -> Option<String> {
+    let lines: Vec<&str> = part.lines().collect();
+    let question = lines.iter().rposition(|l| l.trim_end().ends_with('?'))?;
+    let keywords = ["example", "total", "produces", "=", "password"];
+    for line in lines[..question].iter().rev() {
+        if is_data(line) {
+            continue;
+        }
+        for sentence in line.split(". ").collect::<Vec<_>>().iter().rev() {
+            if !keywords.iter().any(|k| sentence.contains(k)) {
+                continue;
+            }
+            if let Some(n) = last_number(sentence) {
+                return Some(n);
+            }
+        }
+    }
+    None
+}
+
+// Last standalone number, skipping coordinates such as `2,5`.
+fn last_number(sentence: &str) -> Option<String> {
+    let chars: Vec<char> = sentence.chars().collect();
+    let mut found = None;
+    let mut i = 0;
+    while i < chars.len() {
+        if !chars[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < chars.len() && chars[i].is_ascii_digit() {
+            i += 1;
+        }
+        let touches = |c: Option<&char>| c.is_some_and(|c| *c == ',' || c.is_alphanumeric());
+        let before = if start == 0 {
+            None
+        } else {
+            chars.get(start - 1)
+        };
+        let after_comma =
+            chars.get(i) == Some(&',') && chars.get(i + 1).is_some_and(|c| c.is_ascii_digit());
+        if !touches(before) && !after_comma && !chars.get(i).is_some_and(|c| c.is_alphabetic()) {
+            found = Some(chars[start..i].iter().collect());
+        }
+    }
+    found
+}
+
+/// Writes the chosen example block of `challenges/day<day>/readme.md` to
+/// `test.txt` and its stated answers to `expected.txt`. Existing files are
+/// only replaced when `force` is set.
+pub fn extract(
+    root: &Path,
+    day: u32,
+    block: usize,
+    force: bool,
+) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let dir = root.join("challenges").join(format!("day{day}"));
+    let readme = fs::read_to_string(dir.join("readme.md"))?;
+    let blocks = example_blocks(&readme);
+    let example = blocks.get(block).ok_or_else(|| {
+        format!(
+            "readme has {} example blocks, no block {block}",
+            blocks.len()
+        )
+    })?;
+    let expected = expected_answers(&readme);
+
+    let outputs = [
+        (dir.join("test.txt"), example.clone()),
+        (dir.join("expected.txt"), expected.to_file()),
+    ];
+    if let Some((path, _)) = outputs.iter().find(|(p, _)| p.exists())
+        && !force
+    {
+        return Err(format!(
+            "{} already exists, use --force to replace it",
+            path.display()
+        )
+        .into());
+    }
+    let mut written = vec![];
+    for (path, contents) in outputs {
+        fs::write(&path, contents)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn readme(day: u32) -> String {
+        fs::read_to_string(format!("challenges/day{day}/readme.md")).unwrap()
+    }
+
+    #[test]
+    fn expected_answers_from_every_readme() {
+        let answers = [
+            (1, Some("3"), Some("6")),
+            (2, Some("1227775554"), Some("4174379265")),
+            (3, Some("357"), Some("3121910778619")),
+            (4, Some("13"), Some("43")),
+            (5, Some("3"), Some("14")),
+            (6, Some("4277556"), Some("3263827")),
+            (7, Some("21"), Some("40")),
+            (8, Some("40"), Some("25272")),
+            (9, Some("50"), None),
+            (10, Some("7"), None),
+        ];
+        for (day, part1, part2) in answers {
+            let expected = expected_answers(&readme(day));
+            assert_eq!(expected.part1.as_deref(), part1, "day {day}");
+            assert_eq!(expected.part2.as_deref(), part2, "day {day}");
+        }
+    }
+
+    #[test]
+    fn example_blocks_match_test_files() {
+        // Day 5's test.txt has extra ranges added by hand.
+        for day in [4, 6, 7, 8, 9, 10] {
+            let test = fs::read_to_string(format!("challenges/day{day}/test.txt")).unwrap();
+            let blocks = example_blocks(&readme(day));
+            assert_eq!(blocks[0].trim_end(), test.trim_end(), "day {day}");
+        }
+    }
+
+    #[test]
+    fn example_blocks_keeps_inner_blank_line() {
+        let blocks = example_blocks("For example:\n\n1-2\n\n3\nThe end.\n\n#.#\n");
+        assert_eq!(blocks, vec!["1-2\n\n3\n".to_string(), "#.#\n".to_string()]);
+    }
+
+    #[test]
+    fn last_number_skips_coordinates() {
+        let sentence = "this example has area 50 between 2,5 and 11,1";
+        assert_eq!(last_number(sentence), Some("50".to_string()));
+        assert_eq!(last_number("the L68 rotation"), None);
+    }
+
+    #[test]
+    fn expected_round_trips_through_file() {
+        let expected = Expected {
+            part1: Some("50".to_string()),
+            part2: None,
+        };
+        assert_eq!(Expected::parse(&expected.to_file()), expected);
+    }
+}
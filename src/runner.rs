@@ -0,0 +1,485 @@
+use std::fmt::Write;
+use std::fs;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::solution::{Answers, Day};
+
+/// Outcome of running one day against one input file.
+#[derive(Debug, Clone)]
+pub struct DayRun {
+    pub day: &'static Day,
+    pub input_path: String,
+    /// FNV-1a hash of the input, `None` when it could not be read.
+    pub input_hash: Option<u64>,
+    /// `Err` when the input could not be read or parsed.
+    pub result: Result<Answers, String>,
+}
+
+pub fn run_day(day: &'static Day, input_path: &str) -> DayRun {
+    run_day_with(day, input_path, day.run)
+}
+
+/// Same as `run_day`, solving the input with `run` instead of the day's own
+/// runner.
+pub fn run_day_with(
+    day: &'static Day,
+    input_path: &str,
+    run: impl FnOnce(&str) -> Result<Answers, String>,
+) -> DayRun {
+    let (input_hash, result) = match fs::read_to_string(input_path) {
+        Ok(raw) => (Some(fnv1a(raw.as_bytes())), run(&raw)),
+        Err(e) => (None, Err(format!("Problem reading {input_path}: {e}"))),
+    };
+    DayRun {
+        day,
+        input_path: input_path.to_string(),
+        input_hash,
+        result,
+    }
+}
+
+/// 64-bit FNV-1a, enough to tell inputs apart on a dashboard.
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in bytes {
+        hash ^= u64::from(*b);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+/// Runs every day on its own thread against its default input, returning the
+/// runs in day order along with the wall-clock time for the whole batch.
+pub fn run_all(days: &'static [Day]) -> (Vec<DayRun>, Duration) {
+    let start = Instant::now();
+    let runs = thread::scope(|scope| {
+        let handles: Vec<_> = days
+            .iter()
+            .map(|day| scope.spawn(move || run_day(day, &day.input_path())))
+            .collect();
+        handles
+            .into_iter()
+            .zip(days)
+            .map(|(handle, day)| {
+                handle.join().unwrap_or_else(|_| DayRun {
+                    day,
+                    input_path: day.input_path(),
+                    input_hash: None,
+                    result: Err("Solver panicked".to_string()),
+                })
+            })
+            .collect()
+    });
+    (runs, start.elapsed())
+}
+
+pub fn format_duration(d: Duration) -> String {
+    let micros = d.as_micros();
+    if micros < 1_000 {
+        format!("{micros}µs")
+    } else if micros < 1_000_000 {
+        format!("{:.2}ms", d.as_secs_f64() * 1_000.0)
+    } else {
+        format!("{:.2}s", d.as_secs_f64())
+    }
+}
+
+fn answer_cell(answer: &Result<String, String>) -> String {
+    match answer {
+        Ok(a) => a.clone(),
+        Err(e) => format!("error: {e}"),
+    }
+}
+
+/// Lays the runs out as an aligned table followed by a total runtime line.
+pub fn summary_table(runs: &[DayRun], wall_clock: Duration) -> String {
+    let header = [
+        "Day",
+        "Title",
+        "Part 1",
+        "Part 2",
+        "Parse",
+        "Part 1 time",
+        "Part 2 time",
+        "Total",
+    ];
+    let mut rows: Vec<Vec<String>> = vec![header.iter().map(|h| h.to_string()).collect()];
+    let mut solver_time = Duration::ZERO;
+    for run in runs {
+        let mut row = vec![run.day.day.to_string(), run.day.title.to_string()];
+        match &run.result {
+            Ok(answers) => {
+                let t = answers.timings;
+                solver_time += t.total();
+                row.push(answer_cell(&answers.part1));
+                row.push(answer_cell(&answers.part2));
+                for d in [t.parse, t.part1, t.part2, t.total()] {
+                    row.push(format_duration(d));
+                }
+            }
+            Err(e) => {
+                row.push(format!("error: {e}"));
+                row.extend(std::iter::repeat_n(String::new(), 5));
+            }
+        }
+        rows.push(row);
+    }
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|c| rows.iter().map(|r| r[c].chars().count()).max().unwrap_or(0))
+        .collect();
+    let mut out = String::new();
+    for (i, row) in rows.iter().enumerate() {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            // Numbers and timings line up on the right, text on the left.
+            .map(|(c, (cell, w))| match c {
+                1..=3 => format!("{cell:<w$}"),
+                _ => format!("{cell:>w$}"),
+            })
+            .collect();
+        out.push_str(cells.join(" | ").trim_end());
+        out.push('\n');
+        if i == 0 {
+            let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+            out.push_str(&rule.join("-+-"));
+            out.push('\n');
+        }
+    }
+    out.push_str(&format!(
+        "Total runtime: {} wall clock, {} across all solvers\n",
+        format_duration(wall_clock),
+        format_duration(solver_time)
+    ));
+    out
+}
+
+/// Renders runs as JSON. The schema is stable; fields are only ever added.
+///
+/// ```text
+/// {
+///   "schema": 1,
+///   "wall_clock_ns": <integer>,
+///   "days": [
+///     {
+///       "day": <integer>,
+///       "title": <string>,
+///       "input_path": <string>,
+///       "input_hash": <string, 16 hex digit FNV-1a> | null,
+///       "parse_ns": <integer> | null,
+///       "error": <string> | null,
+///       "parts": [
+///         {
+///           "part": <1 or 2>,
+///           "answer": <string> | null,
+///           "elapsed_ns": <integer> | null,
+///           "error": <string> | null
+///         }
+///       ]
+///     }
+///   ]
+/// }
+/// ```
+///
+/// A day level `error` means the input could not be read or parsed, in which
+/// case `parse_ns` is null and every part has a null answer and elapsed time.
+/// Answers are always strings so large values survive JSON number parsing.
+pub fn to_json(runs: &[DayRun], wall_clock: Duration) -> String {
+    let mut out = String::new();
+    write!(
+        out,
+        "{{\"schema\":1,\"wall_clock_ns\":{},\"days\":[",
+        wall_clock.as_nanos()
+    )
+    .unwrap();
+    for (i, run) in runs.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        let hash = run.input_hash.map(|h| json_string(&format!("{h:016x}")));
+        write!(
+            out,
+            "{{\"day\":{},\"title\":{},\"input_path\":{},\"input_hash\":{},",
+            run.day.day,
+            json_string(run.day.title),
+            json_string(&run.input_path),
+            json_or_null(hash)
+        )
+        .unwrap();
+        let (answers, error) = match &run.result {
+            Ok(answers) => (Some(answers), None),
+            Err(e) => (None, Some(json_string(e))),
+        };
+        let parse = answers.map(|a| a.timings.parse.as_nanos().to_string());
+        write!(
+            out,
+            "\"parse_ns\":{},\"error\":{},\"parts\":[",
+            json_or_null(parse),
+            json_or_null(error)
+        )
+        .unwrap();
+        for part in [1, 2] {
+            let (answer, elapsed, error) = match answers {
+                Some(a) => {
+                    let (result, elapsed) = match part {
+                        1 => (&a.part1, a.timings.part1),
+                        _ => (&a.part2, a.timings.part2),
+                    };
+                    let elapsed = Some(elapsed.as_nanos().to_string());
+                    match result {
+                        Ok(answer) => (Some(json_string(answer)), elapsed, None),
+                        Err(e) => (None, elapsed, Some(json_string(e))),
+                    }
+                }
+                None => (None, None, None),
+            };
+            if part > 1 {
+                out.push(',');
+            }
+            write!(
+                out,
+                "{{\"part\":{part},\"answer\":{},\"elapsed_ns\":{},\"error\":{}}}",
+                json_or_null(answer),
+                json_or_null(elapsed),
+                json_or_null(error)
+            )
+            .unwrap();
+        }
+        out.push_str("]}");
+    }
+    out.push_str("]}\n");
+    out
+}
+
+/// How a single day's answers are printed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl Format {
+    /// Takes `--format text|json` out of `args`, text when it is not there.
+    pub fn take(args: &mut Vec<String>) -> Result<Format, &'static str> {
+        let Some(i) = args.iter().position(|a| a == "--format") else {
+            return Ok(Format::Text);
+        };
+        let format = match args.get(i + 1).map(|f| f.as_str()) {
+            Some("text") => Format::Text,
+            Some("json") => Format::Json,
+            _ => return Err("--format expects text or json."),
+        };
+        args.drain(i..i + 2);
+        Ok(format)
+    }
+}
+
+/// One day's answers as `advent_of_code <day>` prints them: a header and a
+/// line per part in text, the single run through `to_json` otherwise.
+pub fn day_report(run: &DayRun, wall_clock: Duration, format: Format) -> String {
+    if format == Format::Json {
+        return to_json(std::slice::from_ref(run), wall_clock);
+    }
+    // An unreadable input gets no header, just the reason.
+    if let (None, Err(e)) = (run.input_hash, &run.result) {
+        return format!("{e}\n");
+    }
+    let mut out = format!("--- Day {}: {} ---\n", run.day.day, run.day.title);
+    match &run.result {
+        Ok(answers) => {
+            for (part, answer) in [(1, &answers.part1), (2, &answers.part2)] {
+                match answer {
+                    Ok(a) => writeln!(out, "Part {part}: {a}").unwrap(),
+                    Err(e) => writeln!(out, "Part {part} error: {e}").unwrap(),
+                }
+            }
+        }
+        Err(e) => writeln!(out, "Parse error: {e}").unwrap(),
+    }
+    out
+}
+
+/// Runs `day` on `input_path` with `run` and prints the `day_report`, after
+/// the input path in text. Shared by `advent_of_code <day>` and the `dayN`
+/// binaries. Returns whether the input could be read and parsed.
+pub fn print_day(
+    day: &'static Day,
+    input_path: &str,
+    format: Format,
+    run: impl FnOnce(&str) -> Result<Answers, String>,
+) -> bool {
+    if format == Format::Text {
+        println!("Loading: {input_path}");
+    }
+    let start = Instant::now();
+    let run = run_day_with(day, input_path, run);
+    print!("{}", day_report(&run, start.elapsed(), format));
+    run.result.is_ok()
+}
+
+fn json_or_null(value: Option<String>) -> String {
+    value.unwrap_or_else(|| "null".to_string())
+}
+
+/// `raw` as a quoted JSON string.
+pub(crate) fn json_string(raw: &str) -> String {
+    let mut out = String::from("\"");
+    for c in raw.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{self, Timings};
+
+    #[test]
+    fn format_duration_picks_units() {
+        assert_eq!(format_duration(Duration::from_micros(250)), "250µs");
+        assert_eq!(format_duration(Duration::from_micros(1_500)), "1.50ms");
+        assert_eq!(format_duration(Duration::from_millis(2_346)), "2.35s");
+    }
+
+    #[test]
+    fn summary_table_aligns_columns() {
+        let timings = Timings {
+            parse: Duration::from_micros(10),
+            part1: Duration::from_micros(10),
+            part2: Duration::from_millis(3),
+        };
+        let runs = vec![
+            DayRun {
+                day: solution::find(1).unwrap(),
+                input_path: "a.txt".to_string(),
+                input_hash: Some(0xab),
+                result: Ok(Answers {
+                    part1: Ok("3".to_string()),
+                    part2: Err("broken".to_string()),
+                    timings,
+                }),
+            },
+            DayRun {
+                day: solution::find(10).unwrap(),
+                input_path: "b.txt".to_string(),
+                input_hash: None,
+                result: Err("Problem reading b.txt".to_string()),
+            },
+        ];
+        let table = summary_table(&runs, Duration::from_millis(4));
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 5);
+        let bar = lines[0].find('|').unwrap();
+        assert!(lines[1..4].iter().all(|l| l[bar..].starts_with(['|', '+'])));
+        assert!(lines[2].contains("error: broken"));
+        assert!(lines[2].ends_with("3.02ms"));
+        assert!(lines[3].contains("error: Problem reading b.txt"));
+        assert!(lines[4].starts_with("Total runtime: 4.00ms"));
+    }
+
+    #[test]
+    fn format_is_taken_out_of_args() {
+        let mut args: Vec<String> = ["day1", "in.txt", "--format", "json", "--csv", "t.csv"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(Format::take(&mut args), Ok(Format::Json));
+        assert_eq!(args, ["day1", "in.txt", "--csv", "t.csv"]);
+        assert_eq!(Format::take(&mut args), Ok(Format::Text));
+        assert!(Format::take(&mut vec!["--format".to_string()]).is_err());
+    }
+
+    #[test]
+    fn day_report_text_matches_cli() {
+        let day = solution::find(1).unwrap();
+        let mut run = DayRun {
+            day,
+            input_path: "a.txt".to_string(),
+            input_hash: Some(0xab),
+            result: Ok(Answers {
+                part1: Ok("3".to_string()),
+                part2: Err("broken".to_string()),
+                timings: Timings::default(),
+            }),
+        };
+        assert_eq!(
+            day_report(&run, Duration::ZERO, Format::Text),
+            "--- Day 1: Secret Entrance ---\nPart 1: 3\nPart 2 error: broken\n"
+        );
+        assert!(day_report(&run, Duration::ZERO, Format::Json).starts_with("{\"schema\":1"));
+        run.result = Err("Bad rotation".to_string());
+        assert_eq!(
+            day_report(&run, Duration::ZERO, Format::Text),
+            "--- Day 1: Secret Entrance ---\nParse error: Bad rotation\n"
+        );
+        run.input_hash = None;
+        run.result = Err("Problem reading a.txt".to_string());
+        assert_eq!(
+            day_report(&run, Duration::ZERO, Format::Text),
+            "Problem reading a.txt\n"
+        );
+    }
+
+    #[test]
+    fn fnv1a_known_values() {
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn json_follows_schema() {
+        let runs = vec![
+            DayRun {
+                day: solution::find(2).unwrap(),
+                input_path: "in \"q\".txt".to_string(),
+                input_hash: Some(0xab),
+                result: Ok(Answers {
+                    part1: Ok("12".to_string()),
+                    part2: Err("nope".to_string()),
+                    timings: Timings {
+                        parse: Duration::from_nanos(1),
+                        part1: Duration::from_nanos(2),
+                        part2: Duration::from_nanos(3),
+                    },
+                }),
+            },
+            DayRun {
+                day: solution::find(3).unwrap(),
+                input_path: "x".to_string(),
+                input_hash: None,
+                result: Err("gone".to_string()),
+            },
+        ];
+        let expected = concat!(
+            r#"{"schema":1,"wall_clock_ns":7,"days":["#,
+            r#"{"day":2,"title":"Gift Shop","input_path":"in \"q\".txt","#,
+            r#""input_hash":"00000000000000ab","parse_ns":1,"error":null,"parts":["#,
+            r#"{"part":1,"answer":"12","elapsed_ns":2,"error":null},"#,
+            r#"{"part":2,"answer":null,"elapsed_ns":3,"error":"nope"}]},"#,
+            r#"{"day":3,"title":"Lobby","input_path":"x","input_hash":null,"#,
+            r#""parse_ns":null,"error":"gone","parts":["#,
+            r#"{"part":1,"answer":null,"elapsed_ns":null,"error":null},"#,
+            r#"{"part":2,"answer":null,"elapsed_ns":null,"error":null}]}]}"#,
+            "\n"
+        );
+        assert_eq!(to_json(&runs, Duration::from_nanos(7)), expected);
+    }
+
+    #[test]
+    fn run_day_reports_missing_input() {
+        let run = run_day(solution::find(1).unwrap(), "challenges/day1/missing.txt");
+        assert!(run.result.unwrap_err().starts_with("Problem reading"));
+    }
+}
@@ -0,0 +1,281 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Widest line rustfmt allows, used when rewrapping the registry imports.
+const MAX_WIDTH: usize = 100;
+
+/// Creates the solver module and challenge folder for a new day and registers
+/// it with the dispatcher. Existing challenge files are left untouched, but an
+/// existing solver module or registration is an error. Returns the files that
+/// were created or changed.
+pub fn new_day(root: &Path, day: u32, title: &str) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    if day == 0 {
+        return Err("Days start at 1".into());
+    }
+    let module = format!("day{day}");
+    let module_path = root.join("src").join(format!("{module}.rs"));
+    let lib_path = root.join("src").join("lib.rs");
+    let registry_path = root.join("src").join("solution.rs");
+
+    // Check everything before writing anything so a refusal leaves no trace.
+    if module_path.exists() {
+        return Err(format!("{} already exists", module_path.display()).into());
+    }
+    let lib = fs::read_to_string(&lib_path)?;
+    if lib
+        .lines()
+        .any(|l| l.trim() == format!("pub mod {module};"))
+    {
+        return Err(format!("{module} is already declared in lib.rs").into());
+    }
+    let registry = fs::read_to_string(&registry_path)?;
+    if registry.contains(&format!("{module}::Solver")) {
+        return Err(format!("{module} is already registered in solution.rs").into());
+    }
+    let lib = register_module(&lib, &module);
+    let registry = register_day(&registry, day, title)?;
+
+    let mut written = vec![];
+    fs::write(&module_path, module_template(day))?;
+    written.push(module_path);
+    fs::write(&lib_path, lib)?;
+    written.push(lib_path);
+    fs::write(&registry_path, registry)?;
+    written.push(registry_path);
+
+    let challenge = root.join("challenges").join(&module);
+    fs::create_dir_all(&challenge)?;
+    let readme = format!("--- Day {day}: {title} ---\n");
+    for (name, contents) in [("input.txt", ""), ("test.txt", ""), ("readme.md", &readme)] {
+        let path = challenge.join(name);
+        if !path.exists() {
+            fs::write(&path, contents)?;
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
+
+fn module_template(day: u32) -> String {
+    format!(
+        r#"use std::error::Error;
+
+use crate::solution::Solution;
+
+pub struct Solver;
+
+impl Solution for Solver {{
+    type Input = Vec<String>;
+
+    fn parse(raw: &str) -> Result<Self::Input, Box<dyn Error>> {{
+        Ok(parse(raw))
+    }}
+
+    fn part1(_input: &Self::Input) -> Result<String, Box<dyn Error>> {{
+        Err("Part 1 is not solved yet".into())
+    }}
+
+    fn part2(_input: &Self::Input) -> Result<String, Box<dyn Error>> {{
+        Err("Part 2 is not solved yet".into())
+    }}
+}}
+
+pub fn parse(raw: &str) -> Vec<String> {{
+    raw.lines().map(|l| l.to_string()).collect()
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn parse_example() {{
+        let raw = fs::read_to_string("challenges/day{day}/test.txt").unwrap();
+        assert_eq!(parse(&raw).len(), raw.lines().count());
+    }}
+}}
+"#
+    )
+}
+
+/// Adds `pub mod <module>;` to the block of module declarations in lib.rs,
+/// keeping them in the order rustfmt would.
+fn register_module(lib: &str, module: &str) -> String {
+    let lines: Vec<&str> = lib.lines().collect();
+    let declaration = format!("pub mod {module};");
+    let is_mod = |l: &str| l.starts_with("pub mod ") && l.ends_with(';');
+    let start = lines.iter().position(|l| is_mod(l)).unwrap_or(0);
+    let end = start + lines[start..].iter().take_while(|l| is_mod(l)).count();
+
+    let mut block: Vec<&str> = lines[start..end].to_vec();
+    block.push(&declaration);
+    block.sort_by_key(|l| l.trim_end_matches(';'));
+
+    let mut out: Vec<&str> = lines[..start].to_vec();
+    out.extend(block);
+    out.extend(&lines[end..]);
+    out.join("\n") + "\n"
+}
+
+/// Adds the day to the `use crate::{..}` import and the `DAYS` table.
+fn register_day(registry: &str, day: u32, title: &str) -> Result<String, Box<dyn Error>> {
+    let start = registry
+        .find("use crate::{")
+        .ok_or("solution.rs has no `use crate::{..}` import")?;
+    let end = start + registry[start..].find("};").ok_or("Unterminated import")? + 2;
+    let mut modules: Vec<String> = registry[start + "use crate::{".len()..end - 2]
+        .split(',')
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+        .collect();
+    modules.push(format!("day{day}"));
+    modules.sort_by_key(|m| {
+        let digits = m.trim_start_matches(|c: char| !c.is_ascii_digit());
+        (
+            m[..m.len() - digits.len()].to_string(),
+            digits.parse::<u32>().ok(),
+        )
+    });
+    let import = format_import(&modules);
+
+    let table = registry
+        .find("pub const DAYS")
+        .ok_or("solution.rs has no DAYS table")?;
+    let table_end = table
+        + registry[table..]
+            .find("];")
+            .ok_or("Unterminated DAYS table")?;
+    // Insert before the first entry for a later day, or at the end.
+    let mut insert_at = table_end;
+    let mut offset = table;
+    for line in registry[table..table_end].split_inclusive('\n') {
+        let existing = line
+            .trim()
+            .strip_prefix("Day::new(")
+            .and_then(|rest| rest.split(',').next())
+            .and_then(|n| n.parse::<u32>().ok());
+        if existing.is_some_and(|n| n > day) {
+            insert_at = offset;
+            break;
+        }
+        offset += line.len();
+    }
+    // Debug formatting quotes and escapes the title as a Rust string literal.
+    let entry = format!("    Day::new({day}, {title:?}, run::<day{day}::Solver>),\n");
+
+    let mut out = String::new();
+    out.push_str(&registry[..start]);
+    out.push_str(&import);
+    out.push_str(&registry[end..insert_at]);
+    out.push_str(&entry);
+    out.push_str(&registry[insert_at..]);
+    Ok(out)
+}
+
+fn format_import(modules: &[String]) -> String {
+    let single = format!("use crate::{{{}}};", modules.join(", "));
+    if single.len() <= MAX_WIDTH {
+        return single;
+    }
+    let mut out = String::from("use crate::{\n");
+    let mut line = String::from("   ");
+    for m in modules {
+        if line.len() + m.len() + 2 > MAX_WIDTH {
+            out.push_str(&line);
+            out.push('\n');
+            line = String::from("   ");
+        }
+        line.push_str(&format!(" {m},"));
+    }
+    out.push_str(&line);
+    out.push_str("\n};");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    const LIB: &str = "pub mod day1;\npub mod day2;\npub mod solution;\n\npub mod util {}\n";
+    const REGISTRY: &str = "use crate::{day1, day2};
+
+pub const DAYS: &[Day] = &[
+    Day::new(1, \"One\", run::<day1::Solver>),
+    Day::new(2, \"Two\", run::<day2::Solver>),
+];
+";
+
+    fn scratch(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("aoc-scaffold-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/lib.rs"), LIB).unwrap();
+        fs::write(root.join("src/solution.rs"), REGISTRY).unwrap();
+        root
+    }
+
+    #[test]
+    fn new_day_creates_and_registers() {
+        let root = scratch("create");
+        let written = new_day(&root, 10, "Ten").unwrap();
+        assert_eq!(written.len(), 6);
+
+        let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        assert!(lib.starts_with("pub mod day1;\npub mod day10;\npub mod day2;\n"));
+        let registry = fs::read_to_string(root.join("src/solution.rs")).unwrap();
+        assert!(registry.starts_with("use crate::{day1, day2, day10};"));
+        assert!(registry.contains(
+            "run::<day2::Solver>),\n    Day::new(10, \"Ten\", run::<day10::Solver>),\n];"
+        ));
+        let module = fs::read_to_string(root.join("src/day10.rs")).unwrap();
+        assert!(module.contains("challenges/day10/test.txt"));
+        assert!(root.join("challenges/day10/input.txt").exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn new_day_escapes_the_title() {
+        let root = scratch("quote");
+        new_day(&root, 11, "Test \"Quote\" \\ Slash").unwrap();
+        let registry = fs::read_to_string(root.join("src/solution.rs")).unwrap();
+        assert!(
+            registry
+                .contains("Day::new(11, \"Test \\\"Quote\\\" \\\\ Slash\", run::<day11::Solver>),")
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn new_day_keeps_existing_challenge_files() {
+        let root = scratch("keep");
+        fs::create_dir_all(root.join("challenges/day3")).unwrap();
+        fs::write(root.join("challenges/day3/input.txt"), "real input").unwrap();
+        let written = new_day(&root, 3, "Three").unwrap();
+        assert!(!written.contains(&root.join("challenges/day3/input.txt")));
+        let input = fs::read_to_string(root.join("challenges/day3/input.txt")).unwrap();
+        assert_eq!(input, "real input");
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn new_day_refuses_registered_days() {
+        let root = scratch("refuse");
+        assert!(new_day(&root, 2, "Two").is_err());
+        fs::write(root.join("src/day4.rs"), "").unwrap();
+        assert!(new_day(&root, 4, "Four").is_err());
+        assert_eq!(fs::read_to_string(root.join("src/lib.rs")).unwrap(), LIB);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn format_import_wraps_long_lists() {
+        let modules: Vec<String> = (1..=30).map(|d| format!("day{d}")).collect();
+        let import = format_import(&modules);
+        assert!(import.starts_with("use crate::{\n    day1, day2,"));
+        assert!(import.lines().all(|l| l.len() <= MAX_WIDTH));
+    }
+}
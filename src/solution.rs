@@ -0,0 +1,220 @@
+use std::error::Error;
+use std::time::{Duration, Instant};
+
+use crate::{day1, day2, day3, day4, day5, day6, day7, day8, day9, day10};
+
+/// Shared interface for a day's puzzle. `parse` turns the raw input into the
+/// day's own representation once, and both parts are solved from that.
+pub trait Solution {
+    type Input;
+
+    fn parse(raw: &str) -> Result<Self::Input, Box<dyn Error>>;
+    fn part1(input: &Self::Input) -> Result<String, Box<dyn Error>>;
+    fn part2(input: &Self::Input) -> Result<String, Box<dyn Error>>;
+}
+
+/// Both answers for a day. Each part can fail on its own.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Answers {
+    pub part1: Result<String, String>,
+    pub part2: Result<String, String>,
+    pub timings: Timings,
+}
+
+/// Time spent in each step of a run.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+/// Type erased entry point for a `Solution`, an `Err` means parsing failed.
+pub type Runner = fn(&str) -> Result<Answers, String>;
+
+pub fn run<S: Solution>(raw: &str) -> Result<Answers, String> {
+    run_with::<S>(raw, S::parse)
+}
+
+/// Same as `run`, with `parse` standing in for `S::parse` so a binary can
+/// apply its own parsing options.
+pub fn run_with<S: Solution>(
+    raw: &str,
+    parse: impl FnOnce(&str) -> Result<S::Input, Box<dyn Error>>,
+) -> Result<Answers, String> {
+    let start = Instant::now();
+    let input = parse(raw).map_err(|e| e.to_string())?;
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let part1 = S::part1(&input).map_err(|e| e.to_string());
+    let part1_time = start.elapsed();
+
+    let start = Instant::now();
+    let part2 = S::part2(&input).map_err(|e| e.to_string());
+    let part2_time = start.elapsed();
+
+    Ok(Answers {
+        part1,
+        part2,
+        timings: Timings {
+            parse,
+            part1: part1_time,
+            part2: part2_time,
+        },
+    })
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub day: u32,
+    pub title: &'static str,
+    pub run: Runner,
+}
+
+impl Day {
+    pub const fn new(day: u32, title: &'static str, run: Runner) -> Day {
+        Day { day, title, run }
+    }
+
+    pub fn input_path(&self) -> String {
+        format!("challenges/day{}/input.txt", self.day)
+    }
+}
+
+/// One timed run of a single part.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PartRun {
+    pub answer: Result<String, String>,
+    pub elapsed: Duration,
+}
+
+/// Type erased entry point for one way of solving a part, an `Err` means
+/// parsing failed.
+pub type PartRunner = fn(&str) -> Result<PartRun, String>;
+
+/// Solves one part from a day's parsed input.
+pub type PartSolver<I> = fn(&I) -> Result<String, Box<dyn Error>>;
+
+/// Parses `raw` for `S` and times `solve` on the result. Parsing is left out
+/// of the timing since every implementation of a day shares it.
+pub fn run_part<S: Solution>(raw: &str, solve: PartSolver<S::Input>) -> Result<PartRun, String> {
+    let input = S::parse(raw).map_err(|e| e.to_string())?;
+    let start = Instant::now();
+    let answer = solve(&input).map_err(|e| e.to_string());
+    Ok(PartRun {
+        answer,
+        elapsed: start.elapsed(),
+    })
+}
+
+/// A named way of solving one part of a day. Days with more than one
+/// approach register each of them so they can be checked against each other.
+#[derive(Debug, Clone, Copy)]
+pub struct Implementation {
+    pub day: u32,
+    pub part: u32,
+    pub name: &'static str,
+    pub run: PartRunner,
+}
+
+impl Implementation {
+    pub const fn new(day: u32, part: u32, name: &'static str, run: PartRunner) -> Implementation {
+        Implementation {
+            day,
+            part,
+            name,
+            run,
+        }
+    }
+}
+
+/// Every registered implementation, grouped by day and part with the one
+/// `DAYS` uses first.
+pub const IMPLEMENTATIONS: &[Implementation] = &[
+    Implementation::new(2, 1, "arithmetic", |raw| {
+        run_part::<day2::Solver>(raw, day2::Solver::part1)
+    }),
+    Implementation::new(2, 1, "strings", |raw| {
+        run_part::<day2::Solver>(raw, |ranges| {
+            Ok(day2::solve_by_strings(ranges)?.0.to_string())
+        })
+    }),
+    Implementation::new(2, 2, "arithmetic", |raw| {
+        run_part::<day2::Solver>(raw, day2::Solver::part2)
+    }),
+    Implementation::new(2, 2, "strings", |raw| {
+        run_part::<day2::Solver>(raw, |ranges| {
+            Ok(day2::solve_by_strings(ranges)?.1.to_string())
+        })
+    }),
+    Implementation::new(5, 1, "binary search", |raw| {
+        run_part::<day5::Solver>(raw, day5::Solver::part1)
+    }),
+    Implementation::new(5, 1, "per item", |raw| {
+        run_part::<day5::Solver>(raw, |inventory| {
+            Ok(day5::fresh_stock_per_item(inventory).to_string())
+        })
+    }),
+    Implementation::new(5, 1, "merged", |raw| {
+        run_part::<day5::Solver>(raw, |inventory| {
+            Ok(day5::fresh_stock_merged(inventory).to_string())
+        })
+    }),
+    Implementation::new(5, 2, "consolidated", |raw| {
+        run_part::<day5::Solver>(raw, day5::Solver::part2)
+    }),
+    Implementation::new(5, 2, "per id", |raw| {
+        run_part::<day5::Solver>(raw, |inventory| {
+            Ok(day5::fresh_ids_per_id(inventory)?.to_string())
+        })
+    }),
+    Implementation::new(8, 1, "sorted edges", |raw| {
+        run_part::<day8::Solver>(raw, day8::Solver::part1)
+    }),
+    Implementation::new(8, 1, "matrix", |raw| {
+        run_part::<day8::Solver>(raw, |playground| {
+            Ok(day8::largest_circuits_matrix(playground, 3)?.to_string())
+        })
+    }),
+    Implementation::new(8, 2, "sorted edges", |raw| {
+        run_part::<day8::Solver>(raw, day8::Solver::part2)
+    }),
+    Implementation::new(8, 2, "matrix", |raw| {
+        run_part::<day8::Solver>(raw, |playground| {
+            Ok(day8::wall_length_matrix(playground)?.to_string())
+        })
+    }),
+];
+
+/// The registered implementations of one part, in registration order.
+pub fn implementations(day: u32, part: u32) -> Vec<&'static Implementation> {
+    IMPLEMENTATIONS
+        .iter()
+        .filter(|i| i.day == day && i.part == part)
+        .collect()
+}
+
+/// Every solved day, in order.
+pub const DAYS: &[Day] = &[
+    Day::new(1, "Secret Entrance", run::<day1::Solver>),
+    Day::new(2, "Gift Shop", run::<day2::Solver>),
+    Day::new(3, "Lobby", run::<day3::Solver>),
+    Day::new(4, "Printing Department", run::<day4::Solver>),
+    Day::new(5, "Cafeteria", run::<day5::Solver>),
+    Day::new(6, "Trash Compactor", run::<day6::Solver>),
+    Day::new(7, "Laboratories", run::<day7::Solver>),
+    Day::new(8, "Playground", run::<day8::Solver>),
+    Day::new(9, "Movie Theater", run::<day9::Solver>),
+    Day::new(10, "Factory", run::<day10::Solver>),
+];
+
+pub fn find(day: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
@@ -0,0 +1,178 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::solution::Answers;
+
+/// Polls a set of files for changes by modification time. A file that is
+/// missing counts as a state of its own, so creating or deleting it is a
+/// change too. A directory is stamped with the newest time found anywhere
+/// under it, so editing, adding or removing a file inside it is a change.
+#[derive(Debug)]
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    stamps: Vec<Option<SystemTime>>,
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Watcher {
+        let stamps = paths.iter().map(|p| modified(p)).collect();
+        Watcher { paths, stamps }
+    }
+
+    /// The files that changed since the last call.
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let mut changed = vec![];
+        for (path, stamp) in self.paths.iter().zip(self.stamps.iter_mut()) {
+            let now = modified(path);
+            if now != *stamp {
+                *stamp = now;
+                changed.push(path.clone());
+            }
+        }
+        changed
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    let meta = fs::metadata(path).ok()?;
+    let own = meta.modified().ok();
+    if !meta.is_dir() {
+        return own;
+    }
+    // The directory's own time moves when entries are added or removed.
+    let entries = fs::read_dir(path).into_iter().flatten().flatten();
+    entries.map(|e| modified(&e.path())).fold(own, Option::max)
+}
+
+fn show(answer: &Result<String, String>) -> String {
+    match answer {
+        Ok(a) => a.clone(),
+        Err(e) => format!("error: {e}"),
+    }
+}
+
+/// One line per part comparing a run with the one before it.
+pub fn diff(previous: Option<&Answers>, current: &Answers) -> Vec<String> {
+    let parts = [(1, &current.part1), (2, &current.part2)];
+    parts
+        .iter()
+        .map(|(part, answer)| {
+            let before = previous.map(|p| if *part == 1 { &p.part1 } else { &p.part2 });
+            match before {
+                None => format!("Part {part}: {}", show(answer)),
+                Some(b) if b == *answer => format!("Part {part}: {} (unchanged)", show(answer)),
+                Some(b) => format!("Part {part}: {} -> {}", show(b), show(answer)),
+            }
+        })
+        .collect()
+}
+
+/// Environment variable carrying the last answers across a re-exec, so the
+/// rebuilt binary can keep diffing against them.
+pub const PREVIOUS_ENV: &str = "ADVENT_OF_CODE_WATCH_PREVIOUS";
+
+// Separators that will not turn up in an answer or error message.
+const PART_SEPARATOR: char = '\u{1e}';
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+/// Both parts' answers as one string for `PREVIOUS_ENV`. Timings are not
+/// kept.
+pub fn encode(answers: &Answers) -> String {
+    [&answers.part1, &answers.part2]
+        .iter()
+        .map(|part| match part {
+            Ok(a) => format!("ok{FIELD_SEPARATOR}{a}"),
+            Err(e) => format!("err{FIELD_SEPARATOR}{e}"),
+        })
+        .collect::<Vec<String>>()
+        .join(&PART_SEPARATOR.to_string())
+}
+
+/// Reads back what `encode` wrote, `None` if it is malformed.
+pub fn decode(raw: &str) -> Option<Answers> {
+    let part = |raw: &str| match raw.split_once(FIELD_SEPARATOR)? {
+        ("ok", a) => Some(Ok(a.to_string())),
+        ("err", e) => Some(Err(e.to_string())),
+        _ => None,
+    };
+    let (part1, part2) = raw.split_once(PART_SEPARATOR)?;
+    Some(Answers {
+        part1: part(part1)?,
+        part2: part(part2)?,
+        timings: Default::default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Timings;
+    use std::env;
+    use std::process;
+
+    fn answers(part1: &str, part2: Result<&str, &str>) -> Answers {
+        Answers {
+            part1: Ok(part1.to_string()),
+            part2: part2.map(str::to_string).map_err(str::to_string),
+            timings: Timings::default(),
+        }
+    }
+
+    #[test]
+    fn encode_round_trips() {
+        for a in [answers("1", Err("not yet")), answers("", Ok("42\nmore"))] {
+            assert_eq!(decode(&encode(&a)), Some(a));
+        }
+        assert_eq!(decode("1"), None);
+        assert_eq!(decode("ok\u{1f}1\u{1e}maybe\u{1f}2"), None);
+    }
+
+    #[test]
+    fn diff_marks_changes() {
+        let first = answers("1", Err("not yet"));
+        assert_eq!(diff(None, &first), ["Part 1: 1", "Part 2: error: not yet"]);
+        let second = answers("1", Ok("7"));
+        assert_eq!(
+            diff(Some(&first), &second),
+            ["Part 1: 1 (unchanged)", "Part 2: error: not yet -> 7"]
+        );
+    }
+
+    #[test]
+    fn watcher_sees_creation_and_removal() {
+        let path = env::temp_dir().join(format!("aoc-watch-{}.txt", process::id()));
+        let _ = fs::remove_file(&path);
+        let mut watcher = Watcher::new(vec![path.clone()]);
+        assert!(watcher.changed().is_empty());
+        fs::write(&path, "1\n").unwrap();
+        assert_eq!(watcher.changed(), std::slice::from_ref(&path));
+        assert!(watcher.changed().is_empty());
+        fs::remove_file(&path).unwrap();
+        assert_eq!(watcher.changed(), [path]);
+    }
+
+    #[test]
+    fn watcher_sees_files_under_a_directory() {
+        let dir = env::temp_dir().join(format!("aoc-watch-dir-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        let file = dir.join("nested").join("a.rs");
+        fs::write(&file, "1\n").unwrap();
+        let mut watcher = Watcher::new(vec![dir.clone()]);
+        assert!(watcher.changed().is_empty());
+        // Push the time forward so the change does not depend on the file
+        // system's timestamp resolution.
+        let later = SystemTime::now() + std::time::Duration::from_secs(60);
+        fs::File::options()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert_eq!(watcher.changed(), std::slice::from_ref(&dir));
+        assert!(watcher.changed().is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(watcher.changed(), [dir]);
+    }
+}